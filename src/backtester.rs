@@ -68,6 +68,332 @@ impl<'a> Scanner<'a> {
     }
 }
 
+struct TradingImpl {
+    me: MatchEngine,
+    rx: std::sync::mpsc::Receiver<Event>,
+
+    /// 已经分发给策略的事件。
+    event: Vec<Event>,
+}
+
+impl TradingImpl {
+    fn new(config: Config) -> Self {
+        let mut me = MatchEngine::new(config);
+        let rx = me.subscribe();
+        Self {
+            me,
+            rx,
+            event: Vec::new(),
+        }
+    }
+
+    /// 取出还没有分发给策略的事件。
+    fn poll(&mut self) -> Vec<Event> {
+        let event = self.rx.try_iter().collect::<Vec<_>>();
+        self.event.extend(event.iter().cloned());
+        event
+    }
+
+    /// 回测结果，历史仓位和事件。
+    fn result(mut self) -> (Vec<Position>, Vec<Event>) {
+        self.poll();
+        (self.me.history().clone(), self.event)
+    }
+}
+
+/// 策略使用的 k 线数据，新的数据在前面。
+struct Data {
+    level: Level,
+    k: Vec<K>,
+    time: Vec<u64>,
+
+    /// k 线的结束时间。
+    end: Vec<u64>,
+
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+}
+
+impl Data {
+    fn new(k: &[K], level: Level) -> Self {
+        Self {
+            level,
+            k: k.to_vec(),
+            time: k.iter().map(|v| v.time).collect(),
+            end: k.iter().map(|v| k_time_convert(v.time, level).1).collect(),
+            open: k.iter().map(|v| v.open).collect(),
+            high: k.iter().map(|v| v.high).collect(),
+            low: k.iter().map(|v| v.low).collect(),
+            close: k.iter().map(|v| v.close).collect(),
+        }
+    }
+
+    /// 从 `index` 开始的数据系列。
+    fn series(&self, index: usize) -> Series<'_> {
+        Series {
+            level: self.level,
+            time: self.time.get(index).copied().unwrap_or(0),
+            open: Source::new(self.open.get(index..).unwrap_or(&[])),
+            high: Source::new(self.high.get(index..).unwrap_or(&[])),
+            low: Source::new(self.low.get(index..).unwrap_or(&[])),
+            close: Source::new(self.close.get(index..).unwrap_or(&[])),
+        }
+    }
+
+    /// 在 `time` 之前已经结束的数据系列。
+    fn series_before(&self, time: u64) -> Series<'_> {
+        self.series(self.end.partition_point(|v| *v > time))
+    }
+}
+
+/// 调用策略的生命周期函数。
+struct Runner<'a> {
+    product: &'a str,
+    min_size: f64,
+    min_notional: f64,
+    data: Data,
+
+    /// 其他时间级别的数据。
+    other: Vec<Data>,
+
+    indicator: Registry,
+    ti: TradingImpl,
+
+    /// 最后一次调用策略的 k 线下标，等于 k 线数量表示还没有调用。
+    index: usize,
+}
+
+impl<'a> Runner<'a> {
+    fn new(
+        product: &'a str,
+        min_size: f64,
+        min_notional: f64,
+        data: Data,
+        other: Vec<Data>,
+        ti: TradingImpl,
+    ) -> Self {
+        Self {
+            product,
+            min_size,
+            min_notional,
+            index: data.time.len(),
+            data,
+            other,
+            indicator: Registry::default(),
+            ti,
+        }
+    }
+
+    /// 当前 k 线的上下文，没有 k 线时数据系列为空，时间为第一根 k 线的时间。
+    fn context(&mut self) -> Context<'_> {
+        let series = self.data.series(self.index);
+        let time = match self.data.time.get(self.index) {
+            Some(v) => *v,
+            None => self.data.time.last().copied().unwrap_or(0),
+        };
+
+        // 没有 k 线时其他时间级别的数据系列也为空
+        let end = self.data.end.get(self.index).copied().unwrap_or(0);
+
+        Context {
+            product: self.product,
+            min_size: self.min_size,
+            min_notional: self.min_notional,
+            level: self.data.level,
+            time,
+            open: series.open,
+            high: series.high,
+            low: series.low,
+            close: series.close,
+            series: self.other.iter().map(|v| v.series_before(end)).collect(),
+            k: self.data.k.get(self.index..).unwrap_or(&[]),
+            indicator: &mut self.indicator,
+            trading: &mut self.ti,
+        }
+    }
+
+    fn on_start<S>(&mut self, strategy: &mut S)
+    where
+        S: Strategy,
+    {
+        strategy.on_start(&mut self.context());
+        self.dispatch(strategy);
+    }
+
+    /// 调用策略，`index` 为结束的 k 线的下标。
+    fn on_bar<S>(&mut self, strategy: &mut S, index: usize)
+    where
+        S: Strategy,
+    {
+        // 按时间顺序把新结束的 k 线输入指标
+        for v in self.data.k[index..self.index].iter().rev() {
+            self.indicator.update(v);
+        }
+
+        self.index = index;
+        strategy.on_bar(&mut self.context());
+        self.dispatch(strategy);
+    }
+
+    /// 把撮合引擎的事件分发给策略，策略在回调中产生的事件也会继续分发。
+    fn dispatch<S>(&mut self, strategy: &mut S)
+    where
+        S: Strategy,
+    {
+        loop {
+            let event = self.ti.poll();
+
+            if event.is_empty() {
+                break;
+            }
+
+            for v in event.iter() {
+                match v {
+                    Event::Close { position, .. } | Event::Liquidate { position, .. } => {
+                        strategy.on_position_closed(&mut self.context(), position)
+                    }
+                    Event::Open { .. }
+                    | Event::Increase { .. }
+                    | Event::Reduce { .. }
+                    | Event::Margin { .. } => {}
+                    _ => strategy.on_order_update(&mut self.context(), v),
+                }
+            }
+        }
+    }
+
+    fn on_finish<S>(mut self, strategy: &mut S) -> (Vec<Position>, Vec<Event>)
+    where
+        S: Strategy,
+    {
+        self.dispatch(strategy);
+        strategy.on_finish(&mut self.context());
+        self.dispatch(strategy);
+        self.ti.result()
+    }
+}
+
+impl Trading for TradingImpl {
+    fn order(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64> {
+        self.me.order(
+            product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
+    fn reduce_only(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+    ) -> anyhow::Result<u64> {
+        self.me.reduce_only(product, side, price, quantity)
+    }
+
+    fn reverse(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64> {
+        self.me.reverse(
+            product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
+    fn cancel(&mut self, id: u64) -> bool {
+        self.me.cancel(id)
+    }
+
+    fn adjust_margin(&mut self, product: &str, margin: f64) -> anyhow::Result<()> {
+        self.me.adjust_margin(product, margin)
+    }
+
+    fn set_lever(&mut self, product: &str, lever: u32) -> anyhow::Result<()> {
+        self.me.set_lever(product, lever)
+    }
+
+    fn amend(
+        &mut self,
+        id: u64,
+        price: f64,
+        quantity: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<()> {
+        self.me.amend(
+            id,
+            price,
+            quantity,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
+    fn balance(&self) -> f64 {
+        self.me.balance()
+    }
+
+    fn delegate(&self, id: u64) -> Option<DelegateState> {
+        self.me.delegate(id)
+    }
+
+    fn order_record(&self, id: u64) -> Option<&OrderRecord> {
+        self.me.order_record(id)
+    }
+
+    fn open_orders(&self, product: &str) -> Vec<&OrderRecord> {
+        self.me.open_orders(product)
+    }
+
+    fn position(&self, product: &str) -> Option<&Position> {
+        self.me.position(product)
+    }
+
+    fn asset(&self, asset: &str) -> f64 {
+        self.me.asset(asset)
+    }
+}
+
 /// 回测器。
 pub struct Backtester<T> {
     exchange: T,
@@ -120,7 +446,7 @@ where
     /// * `return` 回测结果。
     pub async fn start_amplifier<F, S, I>(
         &self,
-        mut strategy: F,
+        strategy: F,
         product: S,
         k_level: Level,
         strategy_level: Level,
@@ -131,61 +457,29 @@ where
         S: AsRef<str>,
         I: Into<TimeRange>,
     {
-        struct TradingImpl {
-            me: MatchEngine,
-        }
-
-        impl TradingImpl {
-            fn new(config: Config) -> Self {
-                Self {
-                    me: MatchEngine::new(config),
-                }
-            }
-        }
-
-        impl Trading for TradingImpl {
-            fn order(
-                &mut self,
-                product: &str,
-                side: Side,
-                price: f64,
-                quantity: Unit,
-                margin: Unit,
-                stop_profit_condition: Unit,
-                stop_loss_condition: Unit,
-                stop_profit: Unit,
-                stop_loss: Unit,
-            ) -> anyhow::Result<u64> {
-                self.me.order(
-                    product,
-                    side,
-                    price,
-                    quantity,
-                    margin,
-                    stop_profit_condition,
-                    stop_loss_condition,
-                    stop_profit,
-                    stop_loss,
-                )
-            }
-
-            fn cancel(&mut self, id: u64) -> bool {
-                self.me.cancel(id)
-            }
-
-            fn balance(&self) -> f64 {
-                self.me.balance()
-            }
-
-            fn delegate(&self, id: u64) -> Option<DelegateState> {
-                self.me.delegate(id)
-            }
-
-            fn position(&self, product: &str) -> Option<&Position> {
-                self.me.position(product)
-            }
-        }
+        self.start_strategy(strategy, product, k_level, strategy_level, range)
+            .await
+            .map(|v| v.0)
+    }
 
+    /// 开始回测，使用带有生命周期的策略，同时收集撮合引擎的事件。
+    /// 参数和 [`Backtester::start_amplifier`] 相同。
+    ///
+    /// * `strategy` 策略，参考 [`Strategy`]，闭包 `|cx: &mut Context| {}` 只实现 [`Strategy::on_bar`]。
+    /// * `return` 回测结果，按时间顺序排列的事件。
+    pub async fn start_strategy<F, S, I>(
+        &self,
+        mut strategy: F,
+        product: S,
+        k_level: Level,
+        strategy_level: Level,
+        range: I,
+    ) -> anyhow::Result<(Vec<Position>, Vec<Event>)>
+    where
+        F: Strategy,
+        S: AsRef<str>,
+        I: Into<TimeRange>,
+    {
         anyhow::ensure!(
             (k_level as u32) <= (strategy_level as u32),
             "product: {}: strategy level must be greater than k level",
@@ -196,6 +490,7 @@ where
         let range = range.into();
         let min_size = self.exchange.get_min_size(product).await?;
         let min_notional = self.exchange.get_min_notional(product).await?;
+        let tier = self.exchange.get_tier(product).await?;
         let k = get_k_range(&self.exchange, product, k_level, range).await?;
         let strategy_k;
 
@@ -206,18 +501,67 @@ where
             strategy_k.as_slice()
         };
 
-        let open = strategy_k.iter().map(|v| v.open).collect::<Vec<_>>();
-        let high = strategy_k.iter().map(|v| v.high).collect::<Vec<_>>();
-        let low = strategy_k.iter().map(|v| v.low).collect::<Vec<_>>();
-        let close = strategy_k.iter().map(|v| v.close).collect::<Vec<_>>();
+        // 用于确定 k 线内部价格路径的小级别 k 线
+        let lower = match self.config.path {
+            PricePath::Lower(level) => {
+                anyhow::ensure!(
+                    (level as u32) < (k_level as u32),
+                    "product: {}: price path level must be less than k level",
+                    product,
+                );
+                get_k_range(&self.exchange, product, level, range).await?
+            }
+            _ => Vec::new(),
+        };
 
-        let mut scanner = Scanner::new(&k, &strategy_k);
+        let mut scanner = Scanner::new(&k, strategy_k);
         let mut ti = TradingImpl::new(self.config);
 
-        ti.me.insert_product(product, min_size, min_notional);
+        if is_spot(product) {
+            ti.me.insert_spot_product(product, min_size, min_notional);
+        } else {
+            ti.me.insert_product(product, min_size, min_notional);
+            ti.me.set_tier(product, tier)?;
+        }
+
+        // 其他时间级别的 k 线，大于等于 k_level 的从 k 线聚合，小于 k_level 的从交易所获取
+        let mut other = Vec::new();
+
+        for level in strategy.levels() {
+            if level == strategy_level || other.iter().any(|v: &Data| v.level == level) {
+                continue;
+            }
+
+            let k = if (level as u32) < (k_level as u32) {
+                get_k_range(&self.exchange, product, level, range).await?
+            } else {
+                k_convert(&k, level)
+            };
+
+            other.push(Data::new(&k, level));
+        }
+
+        let mut runner = Runner::new(
+            product,
+            min_size,
+            min_notional,
+            Data::new(strategy_k, strategy_level),
+            other,
+            ti,
+        );
+
+        runner.on_start(&mut strategy);
 
         for index in (0..k.len()).rev() {
-            ti.me.ready(
+            // k 线按时间从新到旧排列，取出这根 k 线时间范围之内的小级别 k 线
+            let end = if index == 0 {
+                0
+            } else {
+                lower.partition_point(|v| v.time >= k[index - 1].time)
+            };
+            let start = lower.partition_point(|v| v.time >= k[index].time);
+
+            runner.ti.me.ready_lower(
                 product,
                 K {
                     time: k[index].time,
@@ -226,6 +570,7 @@ where
                     low: k[index].low,
                     close: k[index].close,
                 },
+                lower[end..start].iter().rev().copied().collect(),
             );
 
             if let Some((start_index, end_index)) = if k_level == strategy_level {
@@ -234,34 +579,235 @@ where
                 scanner.get()
             } {
                 if index == end_index {
-                    let time = strategy_k[start_index].time;
-                    let open = Source::new(&open[start_index..]);
-                    let high = Source::new(&high[start_index..]);
-                    let low = Source::new(&low[start_index..]);
-                    let close = Source::new(&close[start_index..]);
-
-                    let mut cx = Context {
-                        product,
-                        min_size,
-                        min_notional,
-                        level: strategy_level,
-                        time,
-                        open,
-                        high,
-                        low,
-                        close,
-                        trading: &mut ti,
-                    };
-
-                    strategy(&mut cx);
-
+                    runner.on_bar(&mut strategy, start_index);
                     scanner.next()
                 }
             }
 
-            ti.me.update();
+            runner.ti.me.update();
+            runner.dispatch(&mut strategy);
+        }
+
+        Ok(runner.on_finish(&mut strategy))
+    }
+
+    /// 开始回测规则策略，产品和时间级别使用配置中的设置。
+    ///
+    /// * `config` 规则策略的配置，参考 [`RuleConfig`]。
+    /// * `range` 获取这个时间范围之内的数据，单位毫秒，0 表示获取所有数据，a..b 表示获取 a 到 b 范围的数据。
+    /// * `return` 回测结果，按时间顺序排列的事件。
+    pub async fn start_rule<I>(
+        &self,
+        config: &RuleConfig,
+        range: I,
+    ) -> anyhow::Result<(Vec<Position>, Vec<Event>)>
+    where
+        I: Into<TimeRange>,
+    {
+        self.start_strategy(
+            config.compile()?,
+            &config.product,
+            config.k_level.unwrap_or(config.level),
+            config.level,
+            range,
+        )
+        .await
+    }
+
+    /// 比较回测结果和基准，基准使用相同的时间范围，参考 [`Comparison`]。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `level` 计算权益曲线的时间级别。
+    /// * `range` 获取这个时间范围之内的数据，单位毫秒，0 表示获取所有数据，a..b 表示获取 a 到 b 范围的数据。
+    /// * `history` 回测结果。
+    /// * `benchmark` 基准。
+    /// * `return` 比较结果。
+    pub async fn compare<S, I>(
+        &self,
+        product: S,
+        level: Level,
+        range: I,
+        history: &[Position],
+        benchmark: Benchmark,
+    ) -> anyhow::Result<Comparison>
+    where
+        S: AsRef<str>,
+        I: Into<TimeRange>,
+    {
+        anyhow::ensure!(
+            self.config.initial_margin > 0.0,
+            "product {}: initial margin must be greater than 0",
+            product.as_ref()
+        );
+
+        let range = range.into();
+        let k = get_k_range(&self.exchange, product.as_ref(), level, range).await?;
+        let benchmark = match benchmark {
+            Benchmark::BuyAndHold => k.clone(),
+            Benchmark::Product(v) => get_k_range(&self.exchange, v, level, range).await?,
+            Benchmark::Custom(v) => v
+                .into_iter()
+                // 0 表示所有数据
+                .filter(|v| v.time >= range.start && (range.end == 0 || v.time <= range.end))
+                .collect(),
+        };
+
+        Ok(Comparison::new(
+            equity_curve(&k, history, self.config.initial_margin),
+            benchmark_curve(&k, benchmark, self.config.initial_margin),
+        ))
+    }
+
+    /// 开始逐笔成交回测。
+    /// 每一笔成交都会驱动撮合引擎，同时把成交聚合成 `strategy_level` 时间级别的 k 线，k 线结束后调用策略。
+    ///
+    /// * `strategy` 策略。
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `trade` 逐笔成交数组，新的数据在前面，参考 [`read_trade_csv`]。
+    /// * `strategy_level` 策略的时间级别，即调用策略的时间周期。
+    /// * `return` 回测结果。
+    pub async fn start_trade<F, S, A>(
+        &self,
+        strategy: F,
+        product: S,
+        trade: A,
+        strategy_level: Level,
+    ) -> anyhow::Result<Vec<Position>>
+    where
+        F: FnMut(&mut Context),
+        S: AsRef<str>,
+        A: AsRef<[Trade]>,
+    {
+        let event = trade
+            .as_ref()
+            .iter()
+            .map(|v| BookEvent::Trade(*v))
+            .collect::<Vec<_>>();
+
+        self.start_book(strategy, product, event, strategy_level)
+            .await
+    }
+
+    /// 开始盘口回放回测。
+    /// 撮合引擎使用盘口撮合，参考 [`MatchEngine::ready_book`]。
+    /// 逐笔成交聚合成 `strategy_level` 时间级别的 k 线，k 线结束后调用策略。
+    ///
+    /// * `strategy` 策略。
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `event` 盘口数据和逐笔成交，新的数据在前面。
+    /// * `strategy_level` 策略的时间级别，即调用策略的时间周期。
+    /// * `return` 回测结果。
+    pub async fn start_book<F, S, A>(
+        &self,
+        strategy: F,
+        product: S,
+        event: A,
+        strategy_level: Level,
+    ) -> anyhow::Result<Vec<Position>>
+    where
+        F: FnMut(&mut Context),
+        S: AsRef<str>,
+        A: AsRef<[BookEvent]>,
+    {
+        self.start_book_strategy(strategy, product, event, strategy_level)
+            .await
+            .map(|v| v.0)
+    }
+
+    /// 开始盘口回放回测，使用带有生命周期的策略，同时收集撮合引擎的事件。
+    /// 参数和 [`Backtester::start_book`] 相同。
+    ///
+    /// * `strategy` 策略，参考 [`Strategy`]，闭包 `|cx: &mut Context| {}` 只实现 [`Strategy::on_bar`]。
+    /// * `return` 回测结果，按时间顺序排列的事件。
+    pub async fn start_book_strategy<F, S, A>(
+        &self,
+        mut strategy: F,
+        product: S,
+        event: A,
+        strategy_level: Level,
+    ) -> anyhow::Result<(Vec<Position>, Vec<Event>)>
+    where
+        F: Strategy,
+        S: AsRef<str>,
+        A: AsRef<[BookEvent]>,
+    {
+        let product = product.as_ref();
+        let event = event.as_ref();
+        let min_size = self.exchange.get_min_size(product).await?;
+        let min_notional = self.exchange.get_min_notional(product).await?;
+        let tier = self.exchange.get_tier(product).await?;
+        let trade = event
+            .iter()
+            .filter_map(|v| match v {
+                BookEvent::Trade(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let strategy_k = trade_to_k(&trade, strategy_level);
+
+        let mut ti = TradingImpl::new(self.config);
+
+        if is_spot(product) {
+            ti.me.insert_spot_product(product, min_size, min_notional);
+        } else {
+            ti.me.insert_product(product, min_size, min_notional);
+            ti.me.set_tier(product, tier)?;
+        }
+
+        // 其他时间级别的 k 线从逐笔成交聚合
+        let mut other = Vec::new();
+
+        for level in strategy.levels() {
+            if level != strategy_level && !other.iter().any(|v: &Data| v.level == level) {
+                other.push(Data::new(&trade_to_k(&trade, level), level));
+            }
+        }
+
+        let mut runner = Runner::new(
+            product,
+            min_size,
+            min_notional,
+            Data::new(&strategy_k, strategy_level),
+            other,
+            ti,
+        );
+
+        runner.on_start(&mut strategy);
+
+        // 当前 k 线的下标，等于 k 线数量表示还没有开始
+        let mut index = strategy_k.len();
+
+        // 当前 k 线是否已经调用过策略
+        let mut called = true;
+
+        for v in event.iter().rev() {
+            let time = v.time();
+
+            loop {
+                // 当前 k 线已经结束，调用策略
+                if !called && k_time_convert(strategy_k[index].time, strategy_level).1 <= time {
+                    runner.on_bar(&mut strategy, index);
+                    called = true;
+                }
+
+                // 进入下一根 k 线
+                if index > 0 && strategy_k[index - 1].time <= time {
+                    index -= 1;
+                    called = false;
+                } else {
+                    break;
+                }
+            }
+
+            match v {
+                BookEvent::Book(v) => runner.ti.me.ready_book(product, v),
+                BookEvent::Trade(v) => runner.ti.me.ready_trade(product, v),
+            }
+
+            runner.ti.me.update();
+            runner.dispatch(&mut strategy);
         }
 
-        Ok(ti.me.history().clone())
+        Ok(runner.on_finish(&mut strategy))
     }
 }
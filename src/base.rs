@@ -35,7 +35,7 @@ impl std::fmt::Display for K {
 }
 
 /// 时间级别。
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Level {
     /// 1 分。
     Minute1,
@@ -298,7 +298,7 @@ overload::overload!((a: &Source) / (b: &Source) -> f64 { a[0] / b[0] });
 overload::overload!((a: &Source) % (b: &Source) -> f64 { a[0] % b[0] });
 
 /// 时间范围。
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct TimeRange {
     pub start: u64,
     pub end: u64,
@@ -367,6 +367,65 @@ impl From<std::ops::RangeToInclusive<u64>> for TimeRange {
     }
 }
 
+/// 产品类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Instrument {
+    /// 现货，例如 BTC-USDT。
+    Spot,
+
+    /// 永续合约，例如 BTC-USDT-SWAP。
+    Swap,
+
+    /// 交割合约，例如 BTC-USD-231229。
+    Futures,
+
+    /// 期权，例如 BTC-USD-231229-30000-C。
+    Option,
+}
+
+/// 风险档位。
+/// 仓位价值越大，维持保证金率越高，最大杠杆越低。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tier {
+    /// 档位的最大名义价值，仓位价值小于等于这个值时使用该档位。
+    pub max_notional: f64,
+
+    /// 维持保证金率。
+    pub maintenance: f64,
+
+    /// 最大杠杆。
+    pub max_lever: u32,
+
+    /// 维持保证金速算额。
+    pub maintenance_amount: f64,
+}
+
+/// 逐笔成交。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Trade {
+    /// 成交时间，单位毫秒。
+    pub time: u64,
+
+    /// 成交价格。
+    pub price: f64,
+
+    /// 成交数量。
+    pub size: f64,
+
+    /// 主动成交方向。
+    pub side: TradeSide,
+}
+
+/// 主动成交方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum TradeSide {
+    /// 主动买入。
+    Buy,
+
+    /// 主动卖出。
+    Sell,
+}
+
 /// 委托方向。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Side {
@@ -398,7 +457,7 @@ pub struct Record {
     /// 保证金。
     pub margin: f64,
 
-    /// 手续费。
+    /// 实际收取的手续费，负数表示返佣。
     pub fee: f64,
 
     /// 收益。
@@ -409,6 +468,9 @@ pub struct Record {
 
     /// 时间。
     pub time: u64,
+
+    /// 是否为挂单成交。
+    pub maker: bool,
 }
 
 /// 仓位。
@@ -455,10 +517,14 @@ pub struct Position {
 
     /// 交易记录。
     pub log: Vec<Record>,
+
+    /// 持仓期间的最大不利偏移和最大有利偏移。
+    #[serde(default)]
+    pub excursion: Excursion,
 }
 
 /// 委托。
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Delegate {
     /// 持仓方向。
     pub side: Side,
@@ -477,7 +543,7 @@ pub struct Delegate {
 }
 
 /// 委托状态。
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum DelegateState {
     /// 单个委托。
     Single(Delegate),
@@ -507,8 +573,161 @@ pub enum DelegateState {
     ProfitLoss(Delegate, Delegate),
 }
 
+/// 撮合引擎的事件。
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum Event {
+    /// 委托成功。
+    Accept {
+        id: u64,
+        product: String,
+        time: u64,
+        delegate: DelegateState,
+    },
+
+    /// 委托失败。
+    Reject {
+        product: String,
+        time: u64,
+        reason: String,
+    },
+
+    /// 条件委托被触发，`delegate` 为触发后的委托。
+    Trigger {
+        id: u64,
+        product: String,
+        time: u64,
+        delegate: Delegate,
+    },
+
+    /// 委托成交。
+    Fill {
+        id: u64,
+        product: String,
+        record: Record,
+    },
+
+    /// 委托被修改，`delegate` 为修改后的委托。
+    Amend {
+        id: u64,
+        product: String,
+        time: u64,
+        delegate: DelegateState,
+    },
+
+    /// 委托被撤销。
+    Cancel { id: u64, product: String, time: u64 },
+
+    /// 开仓委托成交时和仓位方向相反，拆分出平仓委托，`id` 为平仓委托，`parent` 为原委托。
+    /// 平仓委托的成交记录到原委托的委托记录。
+    Split {
+        id: u64,
+        parent: u64,
+        product: String,
+        time: u64,
+    },
+
+    /// 委托失效，仓位被平仓或者强平后自动撤销的平仓委托。
+    Expire { id: u64, product: String, time: u64 },
+
+    /// 开仓。
+    Open { time: u64, position: Position },
+
+    /// 加仓。
+    Increase { time: u64, position: Position },
+
+    /// 减仓。
+    Reduce { time: u64, position: Position },
+
+    /// 平仓，`position` 为历史仓位。
+    Close { time: u64, position: Position },
+
+    /// 强平，`position` 为历史仓位。
+    Liquidate { time: u64, position: Position },
+
+    /// 调整保证金。
+    Margin { time: u64, position: Position },
+}
+
+/// 委托状态。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum OrderStatus {
+    /// 等待成交。
+    New,
+
+    /// 条件委托已经触发，等待成交。
+    Triggered,
+
+    /// 部分成交。
+    PartiallyFilled,
+
+    /// 全部成交。
+    Filled,
+
+    /// 已撤销。
+    Cancelled,
+
+    /// 委托失败。
+    Rejected,
+
+    /// 已失效，例如仓位被平仓或者强平后自动撤销的平仓委托。
+    Expired,
+}
+
+/// 委托记录。
+/// 委托的详细结构参考 [`DelegateState`]，成交后的止盈止损委托的成交记录在 `stop_quantity`，`stop_price` 和 `stop_time`。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OrderRecord {
+    /// 委托 id，委托失败时为 0。
+    pub id: u64,
+
+    /// 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    pub product: String,
+
+    /// 委托方向。
+    pub side: Side,
+
+    /// 委托价格。
+    pub price: f64,
+
+    /// 委托数量。
+    pub quantity: f64,
+
+    /// 委托状态。
+    pub status: OrderStatus,
+
+    /// 成交数量。
+    pub fill_quantity: f64,
+
+    /// 成交均价。
+    pub fill_price: f64,
+
+    /// 最后成交时间。
+    pub fill_time: u64,
+
+    /// 止盈止损的成交数量。
+    #[serde(default)]
+    pub stop_quantity: f64,
+
+    /// 止盈止损的成交均价。
+    #[serde(default)]
+    pub stop_price: f64,
+
+    /// 止盈止损的最后成交时间。
+    #[serde(default)]
+    pub stop_time: u64,
+
+    /// 委托时间。
+    pub time: u64,
+
+    /// 最后更新时间。
+    pub update_time: u64,
+
+    /// 委托失败的原因。
+    pub reason: String,
+}
+
 /// 价格。
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum Price {
     /// 大于等于触发价，市价。
     GreaterThanMarket(f64),
@@ -523,6 +742,28 @@ pub enum Price {
     LessThanLimit(f64, f64),
 }
 
+/// 某个时间级别的 k 线数据系列，新的数据在前面，只包含已经结束的 k 线。
+#[derive(Debug, Clone, Copy)]
+pub struct Series<'a> {
+    /// 时间级别。
+    pub level: Level,
+
+    /// 最后一根已经结束的 k 线的时间，没有 k 线时为 0。
+    pub time: u64,
+
+    /// 开盘价数据系列。
+    pub open: &'a Source,
+
+    /// 最高价数据系列。
+    pub high: &'a Source,
+
+    /// 最低价数据系列。
+    pub low: &'a Source,
+
+    /// 收盘价数据系列。
+    pub close: &'a Source,
+}
+
 /// 上下文环境。
 pub struct Context<'a> {
     /// 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
@@ -552,10 +793,88 @@ pub struct Context<'a> {
     /// 收盘价数据系列。
     pub close: &'a Source,
 
+    /// 其他时间级别的数据系列，参考 [`Strategy::levels`]。
+    pub(crate) series: Vec<Series<'a>>,
+
+    /// 已经结束的 k 线，新的数据在前面。
+    pub(crate) k: &'a [K],
+
+    /// 已经注册的指标。
+    pub(crate) indicator: &'a mut Registry,
+
     pub(crate) trading: &'a mut dyn Trading,
 }
 
 impl<'a> Context<'a> {
+    /// 获取某个时间级别的数据系列，只包含在当前 k 线结束之前已经结束的 k 线，不会看到未来的数据。
+    /// 除了策略的时间级别之外，其他时间级别需要在 [`Strategy::levels`] 中声明，否则返回错误。
+    ///
+    /// * `level` 时间级别。
+    pub fn series(&self, level: Level) -> anyhow::Result<Series<'a>> {
+        if level == self.level {
+            return Ok(Series {
+                level,
+                time: self.time,
+                open: self.open,
+                high: self.high,
+                low: self.low,
+                close: self.close,
+            });
+        }
+
+        self.series
+            .iter()
+            .find(|v| v.level == level)
+            .copied()
+            .ok_or(anyhow::anyhow!(
+                "product {}: level {:?} is not declared",
+                self.product,
+                level
+            ))
+    }
+
+    /// 注册增量计算的指标，每根 k 线结束之后，调用策略之前，回测器会自动把这根 k 线输入指标。
+    /// 注册时会使用已经结束的 k 线初始化指标。
+    /// 应该在 [`Strategy::on_start`] 注册指标，如果只能在 [`Strategy::on_bar`] 注册，例如闭包策略，
+    /// 使用相同的名称重复注册会直接返回已经注册的指标，不会再次初始化，`indicator` 和 `input` 会被忽略。
+    ///
+    /// * `key` 指标的名称，例如 `"ema20"`。
+    /// * `indicator` 指标。
+    /// * `input` 从 k 线获取指标的输入，例如 `|k| k.close`。
+    /// * `return` 指标 id，参考 [`Context::indicator`]，名称已经注册为其他类型的指标时返回错误。
+    pub fn register<T, F>(
+        &mut self,
+        key: &str,
+        indicator: T,
+        input: F,
+    ) -> anyhow::Result<IndicatorId<T>>
+    where
+        T: Indicator + 'static,
+        F: FnMut(&K) -> T::Input + 'static,
+    {
+        if let Some(id) = self.indicator.find(key)? {
+            return Ok(id);
+        }
+
+        let id = self.indicator.register(key, indicator, input);
+
+        if let Some(v) = self.indicator.last_mut() {
+            self.k.iter().rev().for_each(|k| v.update(k));
+        }
+
+        Ok(id)
+    }
+
+    /// 获取已经注册的指标。
+    ///
+    /// * `id` 指标 id，参考 [`Context::register`]。
+    pub fn indicator<T>(&self, id: IndicatorId<T>) -> &T
+    where
+        T: 'static,
+    {
+        self.indicator.get(id)
+    }
+
     /// 委托。
     /// 如果做多限价大于市价，那么价格大于等于限价的时候才会成交。
     /// 如果做空限价小于市价，那么价格小于等于限价的时候才会成交。
@@ -755,6 +1074,138 @@ impl<'a> Context<'a> {
         )
     }
 
+    /// 委托，使用仓位计算器计算开仓数量，参考 [`Context::sizer_quantity`]。
+    ///
+    /// * `side` 委托方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `sizer` 仓位计算器。
+    /// * `return` 委托 id。
+    pub fn order_sizer<S>(&mut self, side: Side, price: f64, sizer: &S) -> anyhow::Result<u64>
+    where
+        S: Sizer + ?Sized,
+    {
+        self.order_sizer_condition(
+            side,
+            price,
+            sizer,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+    }
+
+    /// 委托，使用仓位计算器计算开仓数量，并且设置止盈止损，参考 [`Context::order_condition`]。
+    /// 止损触发价格可以使用 [`FixedRisk::stop_loss_condition`] 计算，和计算开仓数量的止损距离一致。
+    ///
+    /// * `side` 委托方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `sizer` 仓位计算器。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_profit` 无效。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_loss` 无效。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `stop_loss` 止损委托格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `return` 委托 id。
+    #[allow(clippy::too_many_arguments)]
+    pub fn order_sizer_condition<S>(
+        &mut self,
+        side: Side,
+        price: f64,
+        sizer: &S,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64>
+    where
+        S: Sizer + ?Sized,
+    {
+        let quantity = self.sizer_quantity(sizer, price);
+
+        anyhow::ensure!(
+            quantity > 0.0,
+            "product {}: sizer quantity less than min size or min notional",
+            self.product
+        );
+
+        self.order_condition(
+            side,
+            price,
+            Unit::Quantity(quantity),
+            Unit::Ignore,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
+    /// 只减仓委托，不会增加仓位，也不会反向开仓，参考 [`MatchEngine::reduce_only`]。
+    ///
+    /// * `side` 委托方向，[`Side::BuyLong`] 和 [`Side::SellLong`] 表示买入减少空仓，[`Side::SellShort`] 和 [`Side::BuySell`] 表示卖出减少多仓。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，单位为币，[`Unit::Ignore`] 表示全部仓位，[`Unit::Proportion`] 表示占用仓位的比例。
+    /// * `return` 委托 id。
+    pub fn reduce_only(&mut self, side: Side, price: f64, quantity: Unit) -> anyhow::Result<u64> {
+        self.trading
+            .reduce_only(self.product, side, price, quantity)
+    }
+
+    /// 反手委托，平掉全部反向的仓位后开仓，参考 [`MatchEngine::reverse`]。
+    ///
+    /// * `side` 开仓方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `return` 委托 id。
+    pub fn reverse(&mut self, side: Side, price: f64) -> anyhow::Result<u64> {
+        self.trading.reverse(
+            self.product,
+            side,
+            price,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+    }
+
+    /// 反手委托，平掉全部反向的仓位后开仓，参考 [`MatchEngine::reverse`]。
+    ///
+    /// * `side` 开仓方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 开仓数量，单位为币，[`Unit::Ignore`] 表示使用 [`Config::quantity`] 的设置。
+    /// * `margin` 开仓保证金，[`Unit::Ignore`] 表示使用 [`Config::margin`] 的设置。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_profit` 无效。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_loss` 无效。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `stop_loss` 止损委托格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `return` 委托 id。
+    #[allow(clippy::too_many_arguments)]
+    pub fn reverse_condition(
+        &mut self,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64> {
+        self.trading.reverse(
+            self.product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
     /// 撤销委托。
     /// 对于已成交的委托，将撤销止盈止损委托。
     ///
@@ -763,11 +1214,96 @@ impl<'a> Context<'a> {
         self.trading.cancel(id)
     }
 
+    /// 调整当前仓位的保证金，参考 [`MatchEngine::adjust_margin`]。
+    ///
+    /// * `margin` 调整的数量，正数表示增加，负数表示减少。
+    pub fn adjust_margin(&mut self, margin: f64) -> anyhow::Result<()> {
+        self.trading.adjust_margin(self.product, margin)
+    }
+
+    /// 设置当前交易产品的杠杆，参考 [`MatchEngine::set_lever`]。
+    ///
+    /// * `lever` 杠杆。
+    pub fn set_lever(&mut self, lever: u32) -> anyhow::Result<()> {
+        self.trading.set_lever(self.product, lever)
+    }
+
+    /// 修改委托的价格，委托 id 和止盈止损不变。
+    ///
+    /// * `id` 委托 id。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    pub fn amend(&mut self, id: u64, price: f64) -> anyhow::Result<()> {
+        self.trading.amend(
+            id,
+            price,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+    }
+
+    /// 修改委托，委托 id 不变。
+    ///
+    /// * `id` 委托 id。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss` 止损委托价格，[`Unit::Ignore`] 表示不修改。
+    #[allow(clippy::too_many_arguments)]
+    pub fn amend_condition(
+        &mut self,
+        id: u64,
+        price: f64,
+        quantity: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<()> {
+        self.trading.amend(
+            id,
+            price,
+            quantity,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        )
+    }
+
     /// 获取余额。
     pub fn balance(&self) -> f64 {
         self.trading.balance()
     }
 
+    /// 获取权益，余额加上当前交易产品仓位的保证金和未实现盈亏。
+    /// 余额已经扣除了未成交委托占用的保证金和手续费，仓位计算器不会重复使用这部分资金。
+    pub fn equity(&self) -> f64 {
+        self.balance() + self.position().map(|v| v.margin + v.profit).unwrap_or(0.0)
+    }
+
+    /// 使用仓位计算器计算开仓数量，按照最小委托数量向下取整，参考 [`round_quantity`]。
+    ///
+    /// * `sizer` 仓位计算器。
+    /// * `price` 开仓价格，0 表示使用当前的收盘价。
+    /// * `return` 数量，单位为币，不满足最小委托数量或者最小名义价值时为 0。
+    pub fn sizer_quantity<S>(&self, sizer: &S, price: f64) -> f64
+    where
+        S: Sizer + ?Sized,
+    {
+        let price = if price == 0.0 { self.close[0] } else { price };
+        round_quantity(
+            sizer.quantity(self, price),
+            price,
+            self.min_size,
+            self.min_notional,
+        )
+    }
+
     /// 获取委托。
     ///
     /// * `product` 委托 id。
@@ -776,12 +1312,37 @@ impl<'a> Context<'a> {
         self.trading.delegate(id)
     }
 
+    /// 获取委托记录。
+    ///
+    /// * `id` 委托 id。
+    /// * `return` 委托记录，如果委托不存在，则返回 None。
+    pub fn order_record(&self, id: u64) -> Option<&OrderRecord> {
+        self.trading.order_record(id)
+    }
+
+    /// 获取当前交易产品还在工作的委托，参考 [`MatchEngine::open_orders`]。
+    pub fn open_orders(&self) -> Vec<&OrderRecord> {
+        self.trading.open_orders(self.product)
+    }
+
     /// 获取仓位。
     ///
     /// * `id` 委托 id。
     pub fn position(&self) -> Option<&Position> {
         self.trading.position(self.product)
     }
+
+    /// 获取现货资产数量。
+    ///
+    /// * `asset` 资产，例如 BTC。
+    pub fn asset(&self, asset: &str) -> f64 {
+        self.trading.asset(asset)
+    }
+
+    /// 识别以当前 k 线结束的 k 线形态，参考 [`patterns`]。
+    pub fn patterns(&self) -> Vec<PatternSignal> {
+        patterns(self.open, self.high, self.low, self.close)
+    }
 }
 
 /// 交易接口。
@@ -823,13 +1384,87 @@ pub trait Trading {
         stop_loss: Unit,
     ) -> anyhow::Result<u64>;
 
+    /// 只减仓委托，参考 [`MatchEngine::reduce_only`]。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `side` 委托方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，单位为币，[`Unit::Ignore`] 表示全部仓位，[`Unit::Proportion`] 表示占用仓位的比例。
+    /// * `return` 委托 id。
+    fn reduce_only(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+    ) -> anyhow::Result<u64>;
+
+    /// 反手委托，参考 [`MatchEngine::reverse`]。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `side` 开仓方向。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 开仓数量，单位为币，[`Unit::Ignore`] 表示使用 [`Config::quantity`] 的设置。
+    /// * `margin` 开仓保证金，[`Unit::Ignore`] 表示使用 [`Config::margin`] 的设置。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_profit` 无效。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_loss` 无效。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `stop_loss` 止损委托格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `return` 委托 id。
+    #[allow(clippy::too_many_arguments)]
+    fn reverse(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64>;
+
     /// 撤销委托。
     /// 对于已成交的委托，将撤销止盈止损委托。
     ///
     /// * `id` 委托 id，0 表示取消所有委托。
     fn cancel(&mut self, id: u64) -> bool;
 
-    /// 获取余额。
+    /// 调整仓位的保证金，参考 [`MatchEngine::adjust_margin`]。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `margin` 调整的数量，正数表示增加，负数表示减少。
+    fn adjust_margin(&mut self, product: &str, margin: f64) -> anyhow::Result<()>;
+
+    /// 设置产品的杠杆，参考 [`MatchEngine::set_lever`]。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `lever` 杠杆。
+    fn set_lever(&mut self, product: &str, lever: u32) -> anyhow::Result<()>;
+
+    /// 修改委托，参考 [`MatchEngine::amend`]。
+    ///
+    /// * `id` 委托 id。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss` 止损委托价格，[`Unit::Ignore`] 表示不修改。
+    #[allow(clippy::too_many_arguments)]
+    fn amend(
+        &mut self,
+        id: u64,
+        price: f64,
+        quantity: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<()>;
+
+    /// 获取余额，需要扣除未成交委托占用的保证金和手续费，参考 [`Context::equity`]。
     fn balance(&self) -> f64;
 
     /// 获取委托。
@@ -838,14 +1473,67 @@ pub trait Trading {
     /// * `return` 委托的状态，如果委托不存在或者已经成交，则返回 None。
     fn delegate(&self, id: u64) -> Option<DelegateState>;
 
+    /// 获取委托记录。
+    ///
+    /// * `id` 委托 id。
+    /// * `return` 委托记录，如果委托不存在，则返回 None。
+    fn order_record(&self, id: u64) -> Option<&OrderRecord>;
+
+    /// 获取还在工作的委托，即等待成交，已经触发和部分成交的委托。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    fn open_orders(&self, product: &str) -> Vec<&OrderRecord>;
+
     /// 获取仓位。
     ///
     /// * `id` 委托 id。
     fn position(&self, product: &str) -> Option<&Position>;
+
+    /// 获取现货资产数量。
+    ///
+    /// * `asset` 资产，例如 BTC。
+    fn asset(&self, asset: &str) -> f64;
+}
+
+/// 策略。
+/// 除了 [`Strategy::on_bar`] 之外都有默认的空实现，闭包 `FnMut(&mut Context)` 自动实现这个 trait，作为 [`Strategy::on_bar`] 调用。
+/// 使用 [`Backtester::start_strategy`] 或者 [`Backtester::start_book_strategy`] 回测。
+/// 回调中的 [`Context`] 只包含已经结束的 k 线，在回调中委托产生的事件也会继续分发。
+pub trait Strategy {
+    /// 策略需要使用的其他时间级别，回测器会自动获取或者聚合这些时间级别的 k 线，参考 [`Context::series`]。
+    fn levels(&self) -> Vec<Level> {
+        Vec::new()
+    }
+
+    /// 开始之前调用一次，数据系列为空。
+    fn on_start(&mut self, _cx: &mut Context) {}
+
+    /// 策略时间级别的 k 线结束时调用。
+    fn on_bar(&mut self, cx: &mut Context);
+
+    /// 委托的状态变化时调用，包括委托，委托失败，触发，成交，修改，撤销和失效，参考 [`Event`]。
+    fn on_order_update(&mut self, _cx: &mut Context, _event: &Event) {}
+
+    /// 仓位被平仓或者强平时调用。
+    ///
+    /// * `position` 历史仓位。
+    fn on_position_closed(&mut self, _cx: &mut Context, _position: &Position) {}
+
+    /// 结束时调用一次。
+    fn on_finish(&mut self, _cx: &mut Context) {}
+}
+
+impl<F> Strategy for F
+where
+    F: FnMut(&mut Context),
+{
+    fn on_bar(&mut self, cx: &mut Context) {
+        self(cx)
+    }
 }
 
 /// 数量，比例
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum Unit {
     /// 忽略。
     Ignore,
@@ -857,8 +1545,35 @@ pub enum Unit {
     Proportion(f64),
 }
 
+/// k 线内部的价格路径。
+/// 一根 k 线无法知道最高价和最低价谁先出现，当同时触发多个委托时，例如止盈和止损，需要确定成交的先后顺序。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PricePath {
+    /// 整根 k 线一次性撮合，成交顺序由撮合引擎的处理顺序决定。
+    Whole,
+
+    /// 开盘价 -> 最高价 -> 最低价 -> 收盘价。
+    OpenHighLowClose,
+
+    /// 开盘价 -> 最低价 -> 最高价 -> 收盘价。
+    OpenLowHighClose,
+
+    /// 先到达离开盘价更近的极值，距离相等时先到达最高价。
+    Nearest,
+
+    /// 先到达对仓位不利的极值，即多仓先到达最低价，空仓先到达最高价。
+    /// 没有仓位时按照挂单的开仓方向，先到达对成交后的仓位有利的极值，使挂单错过有利的价格变动，
+    /// 即买入挂单先到达最高价，卖出挂单先到达最低价。
+    /// 没有挂单或者挂单方向不一致时同 [`PricePath::Nearest`]。
+    Pessimistic,
+
+    /// 使用更小时间级别的 k 线确定路径，每根小级别 k 线内部同 [`PricePath::Nearest`]。
+    /// 没有提供小级别 k 线时同 [`PricePath::Nearest`]。
+    Lower(Level),
+}
+
 /// 交易配置。
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub initial_margin: f64,
     pub lever: u32,
@@ -869,6 +1584,7 @@ pub struct Config {
     pub quantity: Unit,
     pub margin: Unit,
     pub max_margin: Unit,
+    pub path: PricePath,
 }
 
 impl Config {
@@ -883,6 +1599,7 @@ impl Config {
             quantity: Unit::Ignore,
             margin: Unit::Ignore,
             max_margin: Unit::Ignore,
+            path: PricePath::Whole,
         }
     }
 
@@ -893,6 +1610,7 @@ impl Config {
     }
 
     /// 杠杆。
+    /// 插入产品时作为产品的杠杆，之后使用 [`MatchEngine::set_lever`] 单独修改。
     pub fn lever(mut self, value: u32) -> Self {
         self.lever = value;
         self
@@ -953,4 +1671,11 @@ impl Config {
         self.max_margin = value.into();
         self
     }
+
+    /// k 线内部的价格路径。
+    /// 默认为 [`PricePath::Whole`]。
+    pub fn path(mut self, value: PricePath) -> Self {
+        self.path = value;
+        self
+    }
 }
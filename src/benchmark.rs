@@ -0,0 +1,157 @@
+//! 基准比较，计算基准的权益曲线，以及策略相对于基准的 alpha，beta，相关系数，信息比率和超额收益。
+
+use crate::*;
+
+/// 基准。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Benchmark {
+    /// 买入持有交易产品。
+    BuyAndHold,
+
+    /// 买入持有其他产品，从交易所获取 k 线。
+    Product(String),
+
+    /// 自定义的 k 线数据，新的数据在前面。
+    Custom(Vec<K>),
+}
+
+/// 买入持有基准的权益曲线，在第一根 k 线使用全部的初始保证金买入。
+///
+/// * `k` 策略的 k 线数据，用于对齐时间。
+/// * `benchmark` 基准的 k 线数据，每根 k 线使用时间小于等于它的最后一根基准 k 线。
+/// * `initial_margin` 初始保证金。
+/// * `return` 基准的权益，新的数据在前面，和 `k` 对齐，基准开始之前为初始保证金。
+pub fn benchmark_curve<A, B>(k: A, benchmark: B, initial_margin: f64) -> Vec<f64>
+where
+    A: AsRef<[K]>,
+    B: AsRef<[K]>,
+{
+    // 旧的数据在前面
+    let benchmark = benchmark.as_ref().iter().rev().collect::<Vec<_>>();
+    let mut base = f64::NAN;
+
+    k.as_ref()
+        .iter()
+        .rev()
+        .map(|v| {
+            let index = benchmark.partition_point(|b| b.time <= v.time);
+
+            if index == 0 {
+                return initial_margin;
+            }
+
+            let close = benchmark[index - 1].close;
+
+            if base.is_nan() {
+                base = close;
+            }
+
+            initial_margin * close / base
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// 策略相对于基准的比较。
+/// alpha，beta，相关系数和信息比率使用每根 k 线的收益率计算，没有年化。
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Comparison {
+    /// 策略的收益率。
+    pub strategy_return: f64,
+
+    /// 基准的收益率。
+    pub benchmark_return: f64,
+
+    /// 超额收益率，策略的收益率减去基准的收益率。
+    pub excess_return: f64,
+
+    /// 每根 k 线的 alpha，策略收益率中和基准无关的部分。
+    pub alpha: f64,
+
+    /// beta，策略收益率对基准收益率的敏感程度。
+    pub beta: f64,
+
+    /// 收益率的相关系数。
+    pub correlation: f64,
+
+    /// 跟踪误差，超额收益率的标准差。
+    pub tracking_error: f64,
+
+    /// 信息比率，平均超额收益率除以跟踪误差。
+    pub information_ratio: f64,
+
+    /// 基准的权益，新的数据在前面。
+    pub benchmark: Vec<f64>,
+}
+
+impl Comparison {
+    /// 比较策略和基准。
+    ///
+    /// * `equity` 策略的权益，新的数据在前面，参考 [`equity_curve`]。
+    /// * `benchmark` 基准的权益，新的数据在前面，参考 [`benchmark_curve`]。
+    pub fn new<A, B>(equity: A, benchmark: B) -> Self
+    where
+        A: AsRef<[f64]>,
+        B: AsRef<[f64]>,
+    {
+        let equity = equity.as_ref();
+        let benchmark = benchmark.as_ref();
+        let total = |v: &[f64]| match (v.last(), v.first()) {
+            (Some(a), Some(b)) if *a != 0.0 => b / a - 1.0,
+            _ => 0.0,
+        };
+
+        // 旧的数据在前面
+        let (a, b): (Vec<_>, Vec<_>) = equity
+            .windows(2)
+            .zip(benchmark.windows(2))
+            .rev()
+            .map(|(a, b)| (a[0] / a[1] - 1.0, b[0] / b[1] - 1.0))
+            .filter(|(a, b)| a.is_finite() && b.is_finite())
+            .unzip();
+        let excess = a.iter().zip(&b).map(|(a, b)| a - b).collect::<Vec<_>>();
+
+        let ratio = |a: f64, b: f64| if b == 0.0 { 0.0 } else { a / b };
+        let (mean_a, mean_b) = (mean(&a), mean(&b));
+        let cov = covariance(&a, &b);
+        let (var_a, var_b) = (covariance(&a, &a), covariance(&b, &b));
+        let beta = ratio(cov, var_b);
+        let tracking_error = covariance(&excess, &excess).sqrt();
+        let strategy_return = total(equity);
+        let benchmark_return = total(benchmark);
+
+        Self {
+            strategy_return,
+            benchmark_return,
+            excess_return: strategy_return - benchmark_return,
+            alpha: mean_a - beta * mean_b,
+            beta,
+            correlation: ratio(cov, (var_a * var_b).sqrt()),
+            tracking_error,
+            information_ratio: ratio(mean(&excess), tracking_error),
+            benchmark: benchmark.to_vec(),
+        }
+    }
+}
+
+fn mean(value: &[f64]) -> f64 {
+    if value.is_empty() {
+        0.0
+    } else {
+        value.iter().sum::<f64>() / value.len() as f64
+    }
+}
+
+/// 总体协方差。
+fn covariance(a: &[f64], b: &[f64]) -> f64 {
+    let (mean_a, mean_b) = (mean(a), mean(b));
+
+    mean(
+        &a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - mean_a) * (b - mean_b))
+            .collect::<Vec<_>>(),
+    )
+}
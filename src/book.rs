@@ -0,0 +1,145 @@
+use crate::*;
+
+/// 盘口数据，快照或者增量。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BookUpdate {
+    /// 时间，单位毫秒。
+    pub time: u64,
+
+    /// 是否为快照，快照会替换整个盘口，增量只修改对应的价格档位。
+    pub snapshot: bool,
+
+    /// 买盘，价格，数量，增量中数量为 0 表示删除这个价格档位。
+    pub bids: Vec<(f64, f64)>,
+
+    /// 卖盘，价格，数量，增量中数量为 0 表示删除这个价格档位。
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// 盘口回放的事件。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum BookEvent {
+    /// 盘口数据。
+    Book(BookUpdate),
+
+    /// 逐笔成交。
+    Trade(Trade),
+}
+
+impl BookEvent {
+    /// 事件的时间。
+    pub fn time(&self) -> u64 {
+        match self {
+            BookEvent::Book(v) => v.time,
+            BookEvent::Trade(v) => v.time,
+        }
+    }
+}
+
+/// L2 盘口。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OrderBook {
+    /// 更新时间。
+    pub time: u64,
+
+    /// 买盘，价格从高到低排列。
+    pub bids: Vec<(f64, f64)>,
+
+    /// 卖盘，价格从低到高排列。
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 应用快照或者增量。
+    pub fn apply(&mut self, update: &BookUpdate) {
+        self.time = update.time;
+
+        if update.snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        }
+
+        for &(price, size) in update.bids.iter() {
+            Self::apply_level(&mut self.bids, price, size, |a, b| b > a);
+        }
+
+        for &(price, size) in update.asks.iter() {
+            Self::apply_level(&mut self.asks, price, size, |a, b| b < a);
+        }
+    }
+
+    fn apply_level<F>(levels: &mut Vec<(f64, f64)>, price: f64, size: f64, before: F)
+    where
+        F: Fn(f64, f64) -> bool,
+    {
+        match levels.iter().position(|v| !before(price, v.0)) {
+            Some(i) if levels[i].0 == price => {
+                if size == 0.0 {
+                    levels.remove(i);
+                } else {
+                    levels[i].1 = size;
+                }
+            }
+            Some(i) if size != 0.0 => levels.insert(i, (price, size)),
+            None if size != 0.0 => levels.push((price, size)),
+            _ => {}
+        }
+    }
+
+    /// 最优买价。
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|v| v.0)
+    }
+
+    /// 最优卖价。
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|v| v.0)
+    }
+
+    /// 中间价。
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_ask()?) / 2.0)
+    }
+
+    /// 获取价格档位的数量。
+    ///
+    /// * `buy` true 表示买盘，false 表示卖盘。
+    /// * `price` 价格。
+    /// * `return` 数量，没有这个价格档位时返回 0。
+    pub fn size(&self, buy: bool, price: f64) -> f64 {
+        let levels = if buy { &self.bids } else { &self.asks };
+        levels
+            .iter()
+            .find(|v| v.0 == price)
+            .map(|v| v.1)
+            .unwrap_or(0.0)
+    }
+
+    /// 吃单的成交均价。
+    /// 依次吃掉对手盘的每一个价格档位，深度不足时剩余数量按最后一档的价格成交。
+    ///
+    /// * `buy` true 表示买入，吃卖盘，false 表示卖出，吃买盘。
+    /// * `quantity` 数量。
+    /// * `return` 成交均价，对手盘为空时返回 None。
+    pub fn walk(&self, buy: bool, quantity: f64) -> Option<f64> {
+        let levels = if buy { &self.asks } else { &self.bids };
+        let mut remain = quantity;
+        let mut value = 0.0;
+
+        for &(price, size) in levels.iter() {
+            let size = size.min(remain);
+            value += price * size;
+            remain -= size;
+
+            if remain <= 0.0 {
+                return Some(value / quantity);
+            }
+        }
+
+        levels.last().map(|v| (value + v.0 * remain) / quantity)
+    }
+}
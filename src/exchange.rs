@@ -31,17 +31,30 @@ pub trait Exchange {
     where
         S: AsRef<str>,
         S: Send;
+
+    /// 获取风险档位。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `return` 按最大名义价值从小到大排列的档位，返回空数组表示交易所没有提供。
+    async fn get_tier<S>(&self, product: S) -> anyhow::Result<Vec<Tier>>
+    where
+        S: AsRef<str>,
+        S: Send;
 }
 
 /// 本地交易所。
 #[derive(Debug, Clone)]
 pub struct LocalExchange {
     inner: Vec<(String, Level, Vec<K>, f64, f64)>,
+    tier: Vec<(String, Vec<Tier>)>,
 }
 
 impl LocalExchange {
     pub fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            inner: Vec::new(),
+            tier: Vec::new(),
+        }
     }
 
     /// 插入数据。
@@ -71,6 +84,20 @@ impl LocalExchange {
         ));
         self
     }
+
+    /// 插入风险档位。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `tier` 按最大名义价值从小到大排列的档位。
+    pub fn push_tier<S>(mut self, product: S, tier: Vec<Tier>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+        self.tier.retain(|v| v.0 != product);
+        self.tier.push((product.to_string(), tier));
+        self
+    }
 }
 
 impl std::ops::Deref for LocalExchange {
@@ -136,6 +163,20 @@ impl Exchange for LocalExchange {
             .map(|v| v.4)
             .ok_or(anyhow::anyhow!("exchange: no product: {}", product))
     }
+
+    async fn get_tier<S>(&self, product: S) -> anyhow::Result<Vec<Tier>>
+    where
+        S: AsRef<str>,
+        S: Send,
+    {
+        let product = product.as_ref();
+        Ok(self
+            .tier
+            .iter()
+            .find(|v| v.0 == product)
+            .map(|v| v.1.clone())
+            .unwrap_or_default())
+    }
 }
 
 /// 欧易。
@@ -351,6 +392,94 @@ impl Exchange for Okx {
         _ = product;
         Ok(0.0)
     }
+
+    async fn get_tier<S>(&self, product: S) -> anyhow::Result<Vec<Tier>>
+    where
+        S: AsRef<str>,
+        S: Send,
+    {
+        let product = product.as_ref();
+
+        let product = if product.contains("-") {
+            product.into()
+        } else {
+            product_mapping(product)
+        };
+
+        if !product.contains("SWAP") {
+            return Ok(Vec::new());
+        }
+
+        let result = self
+            .client
+            .get(self.base_url.clone() + "/api/v5/public/position-tiers")
+            .query(&serde_json::json!({
+                "instType": "SWAP",
+                "tdMode": "isolated",
+                "instFamily": product.trim_end_matches("-SWAP"),
+            }))
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await?;
+
+        anyhow::ensure!(result["code"] == "0", result.to_string());
+
+        let array = result["data"]
+            .as_array()
+            .ok_or(anyhow::anyhow!("interface exception"))?;
+
+        // 欧易的档位以张数划分，按照面值和最新价格换算到名义价值
+        let ct_val = self.get_min_size(product.as_ref()).await?;
+
+        let ticker = self
+            .client
+            .get(self.base_url.clone() + "/api/v5/market/ticker")
+            .query(&serde_json::json!({ "instId": product }))
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await?;
+
+        anyhow::ensure!(ticker["code"] == "0", ticker.to_string());
+
+        let last = ticker["data"][0]["last"]
+            .as_str()
+            .ok_or(anyhow::anyhow!("interface exception"))?
+            .parse::<f64>()?;
+
+        let mut result = Vec::with_capacity(array.len());
+
+        for i in array {
+            result.push(Tier {
+                max_notional: i["maxSz"]
+                    .as_str()
+                    .ok_or(anyhow::anyhow!("interface exception"))?
+                    .parse::<f64>()?
+                    * ct_val
+                    * last,
+                maintenance: i["mmr"]
+                    .as_str()
+                    .ok_or(anyhow::anyhow!("interface exception"))?
+                    .parse::<f64>()?,
+                max_lever: i["maxLever"]
+                    .as_str()
+                    .ok_or(anyhow::anyhow!("interface exception"))?
+                    .parse::<f64>()? as u32,
+                maintenance_amount: 0.0,
+            });
+        }
+
+        result.sort_by(|a, b| a.max_notional.total_cmp(&b.max_notional));
+
+        // 欧易没有提供速算额，按照档位边界连续的原则计算
+        for i in 1..result.len() {
+            result[i].maintenance_amount = result[i - 1].maintenance_amount
+                + result[i - 1].max_notional * (result[i].maintenance - result[i - 1].maintenance);
+        }
+
+        Ok(result)
+    }
 }
 
 /// 币安。
@@ -621,4 +750,14 @@ impl crate::Exchange for Binance {
             })
             .ok_or(anyhow::anyhow!("exchange: no product: {}", product))
     }
+
+    async fn get_tier<S>(&self, product: S) -> anyhow::Result<Vec<Tier>>
+    where
+        S: AsRef<str>,
+        S: Send,
+    {
+        // 币安的杠杆分层标准接口需要签名
+        _ = product;
+        Ok(Vec::new())
+    }
 }
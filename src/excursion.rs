@@ -0,0 +1,145 @@
+//! 持仓期间的最大不利偏移 (MAE) 和最大有利偏移 (MFE)，以及持仓时间，用于调整止盈止损。
+
+use crate::*;
+
+/// 持仓期间的价格偏移和持仓时间，由撮合引擎在每根 k 线使用最高价和最低价更新，
+/// 开仓的 k 线只使用收盘价，仓位进入历史仓位时再使用平仓的成交价格更新。
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Excursion {
+    /// 最大不利偏移的价格，多仓为持仓期间的最低价，空仓为最高价。
+    pub adverse_price: f64,
+
+    /// 最大不利偏移的盈亏，小于等于 0。
+    pub adverse_profit: f64,
+
+    /// 开仓之后到达最大不利偏移的时间，单位毫秒。
+    pub adverse_time: u64,
+
+    /// 最大有利偏移的价格，多仓为持仓期间的最高价，空仓为最低价。
+    pub favorable_price: f64,
+
+    /// 最大有利偏移的盈亏，大于等于 0。
+    pub favorable_profit: f64,
+
+    /// 开仓之后到达最大有利偏移的时间，单位毫秒。
+    pub favorable_time: u64,
+
+    /// 持仓经过的 k 线数量，策略以收盘价开仓时不包括开仓的 k 线。
+    /// 使用盘口或者逐笔成交撮合时按照 1 分钟的 k 线统计。
+    pub bars: u64,
+
+    /// 最后一次统计的 k 线的开始时间。
+    #[serde(skip)]
+    pub(crate) bar: Option<u64>,
+}
+
+impl Excursion {
+    pub(crate) fn new(price: f64) -> Self {
+        Self {
+            adverse_price: price,
+            favorable_price: price,
+            ..Default::default()
+        }
+    }
+
+    /// 进入新的 k 线时增加持仓经过的 k 线数量，同一根 k 线多次撮合只统计一次。
+    ///
+    /// * `bar` k 线的开始时间。
+    pub(crate) fn count(&mut self, bar: u64) {
+        if self.bar != Some(bar) {
+            self.bar = Some(bar);
+            self.bars += 1;
+        }
+    }
+
+    /// 使用新的价格更新偏移。
+    ///
+    /// * `position` 仓位。
+    /// * `quantity` 持仓量。
+    /// * `price` 价格。
+    /// * `time` 价格的时间。
+    pub(crate) fn update(&mut self, position: &Position, quantity: f64, price: f64, time: u64) {
+        let profit = if position.side == Side::BuyLong {
+            price - position.open_price
+        } else {
+            position.open_price - price
+        } * quantity;
+        let time = time.saturating_sub(position.open_time);
+
+        if profit < self.adverse_profit {
+            self.adverse_price = price;
+            self.adverse_profit = profit;
+            self.adverse_time = time;
+        }
+
+        if profit > self.favorable_profit {
+            self.favorable_price = price;
+            self.favorable_profit = profit;
+            self.favorable_time = time;
+        }
+    }
+}
+
+/// 散点图的一个点，一个历史仓位为一个点。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExcursionPoint {
+    /// 持仓方向。
+    pub side: Side,
+
+    /// 最大不利偏移的盈亏。
+    pub adverse: f64,
+
+    /// 最大不利偏移相对于开仓均价的比例，小于等于 0，可以直接用于 [`Unit::Proportion`] 的止损。
+    pub adverse_ratio: f64,
+
+    /// 最大有利偏移的盈亏。
+    pub favorable: f64,
+
+    /// 最大有利偏移相对于开仓均价的比例，大于等于 0，可以直接用于 [`Unit::Proportion`] 的止盈。
+    pub favorable_ratio: f64,
+
+    /// 净收益，扣除手续费。
+    pub profit: f64,
+
+    /// 持仓经过的 k 线数量。
+    pub bars: u64,
+
+    /// 开仓之后到达最大有利偏移的时间，单位毫秒。
+    pub favorable_time: u64,
+}
+
+/// 历史仓位的最大不利偏移和最大有利偏移，用于绘制散点图。
+///
+/// * `history` 历史仓位。
+/// * `return` 和历史仓位一一对应。
+pub fn excursion_points<T>(history: T) -> Vec<ExcursionPoint>
+where
+    T: AsRef<[Position]>,
+{
+    history
+        .as_ref()
+        .iter()
+        .map(|v| {
+            let ratio = |price: f64| {
+                if v.open_price == 0.0 {
+                    0.0
+                } else if v.side == Side::BuyLong {
+                    price / v.open_price - 1.0
+                } else {
+                    1.0 - price / v.open_price
+                }
+            };
+
+            ExcursionPoint {
+                side: v.side,
+                adverse: v.excursion.adverse_profit,
+                adverse_ratio: ratio(v.excursion.adverse_price).min(0.0),
+                favorable: v.excursion.favorable_profit,
+                favorable_ratio: ratio(v.excursion.favorable_price).max(0.0),
+                profit: v.profit - v.fee,
+                bars: v.excursion.bars,
+                favorable_time: v.excursion.favorable_time,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,322 @@
+//! 导出回测结果，交易列表，成交记录和权益曲线导出为 csv，全部结果导出为一个 json 文件。
+//! csv 按照时间从旧到新排列，时间为毫秒时间戳。
+
+use crate::*;
+use std::io::Write;
+
+/// 权益曲线上的一个点。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EquityPoint {
+    /// k 线时间。
+    pub time: u64,
+
+    /// 收盘价。
+    pub close: f64,
+
+    /// 权益。
+    pub equity: f64,
+
+    /// 回撤。
+    pub drawdown: f64,
+}
+
+/// 计算权益曲线，参考 [`equity_curve`] 和 [`drawdown`]。
+///
+/// * `k` k 线数据。
+/// * `history` 历史仓位。
+/// * `initial_margin` 初始保证金。
+/// * `return` 权益曲线，旧的数据在前面。
+pub fn equity_points<A, B>(k: A, history: B, initial_margin: f64) -> Vec<EquityPoint>
+where
+    A: AsRef<[K]>,
+    B: AsRef<[Position]>,
+{
+    let k = k.as_ref();
+    let equity = equity_curve(k, history, initial_margin);
+    let drawdown = drawdown(&equity);
+
+    k.iter()
+        .zip(equity)
+        .zip(drawdown)
+        .rev()
+        .map(|((k, equity), drawdown)| EquityPoint {
+            time: k.time,
+            close: k.close,
+            equity,
+            drawdown,
+        })
+        .collect()
+}
+
+/// 写入交易列表，一个历史仓位为一行。
+///
+/// * `writer` 输出。
+/// * `history` 历史仓位。
+pub fn write_trades_csv<W, B>(mut writer: W, history: B) -> anyhow::Result<()>
+where
+    W: Write,
+    B: AsRef<[Position]>,
+{
+    writeln!(
+        writer,
+        "id,product,lever,side,open_time,close_time,open_price,close_price,quantity,margin,liquidation_price,profit,fee,net_profit,profit_ratio,fills"
+    )?;
+
+    for (index, v) in history.as_ref().iter().enumerate() {
+        writeln!(
+            writer,
+            "{},{},{},{:?},{},{},{},{},{},{},{},{},{},{},{},{}",
+            index,
+            csv_field(&v.product),
+            v.lever,
+            v.side,
+            v.open_time,
+            v.close_time,
+            v.open_price,
+            v.close_price,
+            v.quantity,
+            v.margin,
+            v.liquidation_price,
+            v.profit,
+            v.fee,
+            v.profit - v.fee,
+            v.profit_ratio,
+            v.log.len(),
+        )?;
+    }
+
+    Ok(writer.flush()?)
+}
+
+/// 写入成交记录，一个交易记录为一行，`position` 为交易列表中的 `id`。
+///
+/// * `writer` 输出。
+/// * `history` 历史仓位。
+pub fn write_fills_csv<W, B>(mut writer: W, history: B) -> anyhow::Result<()>
+where
+    W: Write,
+    B: AsRef<[Position]>,
+{
+    writeln!(
+        writer,
+        "position,product,side,time,price,quantity,margin,fee,profit,profit_ratio,maker"
+    )?;
+
+    for (index, p) in history.as_ref().iter().enumerate() {
+        for v in &p.log {
+            writeln!(
+                writer,
+                "{},{},{:?},{},{},{},{},{},{},{},{}",
+                index,
+                csv_field(&p.product),
+                v.side,
+                v.time,
+                v.price,
+                v.quantity,
+                v.margin,
+                v.fee,
+                v.profit,
+                v.profit_ratio,
+                v.maker,
+            )?;
+        }
+    }
+
+    Ok(writer.flush()?)
+}
+
+/// 写入权益曲线。
+///
+/// * `writer` 输出。
+/// * `equity` 权益曲线，参考 [`equity_points`]。
+pub fn write_equity_csv<W, T>(mut writer: W, equity: T) -> anyhow::Result<()>
+where
+    W: Write,
+    T: AsRef<[EquityPoint]>,
+{
+    writeln!(writer, "time,close,equity,drawdown")?;
+
+    for v in equity.as_ref() {
+        writeln!(writer, "{},{},{},{}", v.time, v.close, v.equity, v.drawdown)?;
+    }
+
+    Ok(writer.flush()?)
+}
+
+/// 包含逗号，引号或者换行时使用引号包围。
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        std::borrow::Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        std::borrow::Cow::Borrowed(value)
+    }
+}
+
+/// 回测的元数据。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Metadata {
+    /// 交易产品。
+    pub product: String,
+
+    /// k 线的时间级别。
+    pub k_level: Level,
+
+    /// 策略的时间级别。
+    pub strategy_level: Level,
+
+    /// 回测的时间范围。
+    pub range: TimeRange,
+
+    /// 框架的版本。
+    pub version: String,
+
+    /// 导出的时间。
+    pub time: u64,
+}
+
+impl Metadata {
+    /// 创建元数据，导出的时间为当前时间。
+    ///
+    /// * `product` 交易产品。
+    /// * `k_level` k 线的时间级别。
+    /// * `strategy_level` 策略的时间级别。
+    /// * `range` 回测的时间范围。
+    pub fn new<S, I>(product: S, k_level: Level, strategy_level: Level, range: I) -> Self
+    where
+        S: AsRef<str>,
+        I: Into<TimeRange>,
+    {
+        Self {
+            product: product.as_ref().to_string(),
+            k_level,
+            strategy_level,
+            range: range.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            time: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |v| v.as_millis() as u64),
+        }
+    }
+}
+
+/// 全部的回测结果，用于归档和分析。
+///
+/// ```no_run
+/// # use auto_trading::*;
+/// # async fn f(backtester: Backtester<LocalExchange>, config: Config, k: Vec<K>) {
+/// let (history, _) = backtester
+///     .start_strategy(|_: &mut Context| {}, "BTC-USDT-SWAP", Level::Minute1, Level::Hour4, 0)
+///     .await
+///     .unwrap();
+///
+/// let bundle = Bundle::new(
+///     Metadata::new("BTC-USDT-SWAP", Level::Minute1, Level::Hour4, 0),
+///     config,
+///     &k,
+///     history,
+/// );
+/// bundle.save("result.json").unwrap();
+/// bundle.save_csv("result").unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Bundle {
+    /// 元数据。
+    pub metadata: Metadata,
+
+    /// 交易配置。
+    pub config: Config,
+
+    /// 统计指标。
+    pub summary: Summary,
+
+    /// 历史仓位。
+    pub history: Vec<Position>,
+
+    /// 权益曲线，旧的数据在前面。
+    pub equity: Vec<EquityPoint>,
+}
+
+impl Bundle {
+    /// 创建回测结果，使用 [`Config::initial_margin`] 计算统计指标和权益曲线。
+    ///
+    /// * `metadata` 元数据。
+    /// * `config` 交易配置。
+    /// * `k` k 线数据。
+    /// * `history` 历史仓位。
+    pub fn new<A>(metadata: Metadata, config: Config, k: A, history: Vec<Position>) -> Self
+    where
+        A: AsRef<[K]>,
+    {
+        let k = k.as_ref();
+
+        Self {
+            metadata,
+            config,
+            summary: Summary::new(k, &history, config.initial_margin),
+            equity: equity_points(k, &history, config.initial_margin),
+            history,
+        }
+    }
+
+    /// 转换到 json 文本。
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从 json 文本解析。
+    pub fn from_json<S>(text: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Ok(serde_json::from_str(text.as_ref())?)
+    }
+
+    /// 保存到 json 文件。
+    ///
+    /// * `path` 文件路径。
+    pub fn save<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()?)
+            .map_err(|e| anyhow::anyhow!("write {}: {}", path.display(), e))
+    }
+
+    /// 读取 json 文件。
+    ///
+    /// * `path` 文件路径。
+    pub fn load<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("read {}: {}", path.display(), e))?;
+        Self::from_json(text)
+    }
+
+    /// 在目录中保存 trades.csv，fills.csv 和 equity.csv，目录不存在时自动创建。
+    ///
+    /// * `dir` 目录。
+    pub fn save_csv<P>(&self, dir: P) -> anyhow::Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)
+            .map_err(|e| anyhow::anyhow!("create {}: {}", dir.display(), e))?;
+
+        let create = |name: &str| {
+            let path = dir.join(name);
+            std::fs::File::create(&path)
+                .map(std::io::BufWriter::new)
+                .map_err(|e| anyhow::anyhow!("create {}: {}", path.display(), e))
+        };
+
+        write_trades_csv(create("trades.csv")?, &self.history)?;
+        write_fills_csv(create("fills.csv")?, &self.history)?;
+        write_equity_csv(create("equity.csv")?, &self.equity)
+    }
+}
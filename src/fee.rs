@@ -0,0 +1,121 @@
+use crate::*;
+
+/// 手续费模型。
+/// 每次成交时根据手续费率计算手续费，手续费等于成交额乘以手续费率。
+pub trait Fee: std::fmt::Debug + Send + Sync {
+    /// 获取手续费率。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `side` 成交方向。
+    /// * `maker` 是否为挂单成交。
+    /// * `volume` 过去 30 天的成交额，不包括本次成交。
+    /// * `return` 手续费率，负数表示返佣。
+    fn rate(&self, product: &str, side: Side, maker: bool, volume: f64) -> f64;
+}
+
+/// 手续费等级。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FeeTier {
+    /// 过去 30 天的最小成交额。
+    pub min_volume: f64,
+
+    /// 挂单手续费率，负数表示返佣。
+    pub maker: f64,
+
+    /// 吃单手续费率。
+    pub taker: f64,
+}
+
+/// 分级手续费。
+/// 按照过去 30 天的成交额确定等级，产品单独设置的费率优先于等级。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FeeSchedule {
+    /// 按最小成交额从小到大排列的等级。
+    pub tier: Vec<FeeTier>,
+
+    /// 产品，挂单手续费率，吃单手续费率。
+    pub product: Vec<(String, f64, f64)>,
+
+    /// 手续费折扣，例如 0.2 表示手续费减少 20%，不影响返佣。
+    pub discount: f64,
+}
+
+impl FeeSchedule {
+    /// 构造分级手续费。
+    ///
+    /// * `maker` 默认的挂单手续费率，负数表示返佣。
+    /// * `taker` 默认的吃单手续费率。
+    pub fn new(maker: f64, taker: f64) -> Self {
+        Self {
+            tier: vec![FeeTier {
+                min_volume: 0.0,
+                maker,
+                taker,
+            }],
+            product: Vec::new(),
+            discount: 0.0,
+        }
+    }
+
+    /// 增加等级，过去 30 天的成交额大于等于 `min_volume` 时使用。
+    pub fn tier(mut self, min_volume: f64, maker: f64, taker: f64) -> Self {
+        self.tier.retain(|v| v.min_volume != min_volume);
+
+        let index = self
+            .tier
+            .iter()
+            .position(|v| v.min_volume > min_volume)
+            .unwrap_or(self.tier.len());
+
+        self.tier.insert(
+            index,
+            FeeTier {
+                min_volume,
+                maker,
+                taker,
+            },
+        );
+        self
+    }
+
+    /// 产品单独设置的费率，不受等级影响。
+    pub fn product<S>(mut self, product: S, maker: f64, taker: f64) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+        self.product.retain(|v| v.0 != product);
+        self.product.push((product.to_string(), maker, taker));
+        self
+    }
+
+    /// 手续费折扣，例如 0.2 表示手续费减少 20%，不影响返佣。
+    pub fn discount(mut self, value: f64) -> Self {
+        self.discount = value;
+        self
+    }
+}
+
+impl Fee for FeeSchedule {
+    fn rate(&self, product: &str, _: Side, maker: bool, volume: f64) -> f64 {
+        let (maker_rate, taker_rate) = match self.product.iter().find(|v| v.0 == product) {
+            Some(v) => (v.1, v.2),
+            None => self
+                .tier
+                .iter()
+                .rev()
+                .find(|v| volume >= v.min_volume)
+                .or(self.tier.first())
+                .map(|v| (v.maker, v.taker))
+                .unwrap_or((0.0, 0.0)),
+        };
+
+        let rate = if maker { maker_rate } else { taker_rate };
+
+        if rate > 0.0 {
+            rate * (1.0 - self.discount)
+        } else {
+            rate
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! 增量计算的指标，每次输入一个新的值，只保存计算需要的状态，不需要重新计算整个数据系列。
+//! 指标的结果和 [`ema`]，[`rma`]，[`macd`] 等函数对整个数据系列计算的结果一致。
+
+use crate::*;
+use std::collections::VecDeque;
+
+/// 增量计算的指标。
+pub trait Indicator {
+    /// 输入，f64 或者 [`K`]。
+    type Input;
+
+    /// 输出。
+    type Output;
+
+    /// 输入一个新的值，按时间从旧到新的顺序输入。
+    ///
+    /// * `input` 新的值。
+    /// * `return` 计算结果，数据不足时为 NaN。
+    fn update(&mut self, input: Self::Input) -> Self::Output;
+
+    /// 最后一次计算的结果，没有输入时为 NaN。
+    fn value(&self) -> Self::Output;
+}
+
+/// 固定长度的窗口，新的数据在前面。
+#[derive(Debug, Clone)]
+struct Window {
+    length: usize,
+    inner: VecDeque<f64>,
+}
+
+impl Window {
+    fn new(length: usize) -> Self {
+        Self {
+            length,
+            inner: VecDeque::with_capacity(length + 1),
+        }
+    }
+
+    /// 放入新的值，返回被移出窗口的值。
+    fn push(&mut self, value: f64) -> Option<f64> {
+        self.inner.push_front(value);
+
+        if self.inner.len() > self.length {
+            self.inner.pop_back()
+        } else {
+            None
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.inner.len() >= self.length
+    }
+}
+
+/// 单调队列，用于在固定长度的窗口里求最值，每次输入均摊 O(1)。
+/// 队列从前到后按输入顺序排列，保存输入的序号和值，NaN 不会进入队列。
+#[derive(Debug, Clone)]
+struct Monotonic {
+    length: usize,
+    count: usize,
+    inner: VecDeque<(usize, f64)>,
+}
+
+impl Monotonic {
+    fn new(length: usize) -> Self {
+        Self {
+            length,
+            count: 0,
+            inner: VecDeque::new(),
+        }
+    }
+
+    /// 放入新的值。
+    ///
+    /// * `keep` 队尾的值是否保留，返回 false 时移除队尾的值。
+    fn push<F>(&mut self, value: f64, keep: F)
+    where
+        F: Fn(f64) -> bool,
+    {
+        let index = self.count;
+        self.count += 1;
+
+        if !value.is_nan() {
+            while self.inner.back().is_some_and(|v| !keep(v.1)) {
+                self.inner.pop_back();
+            }
+
+            self.inner.push_back((index, value));
+        }
+
+        while self
+            .inner
+            .front()
+            .is_some_and(|v| v.0 + self.length <= index)
+        {
+            self.inner.pop_front();
+        }
+    }
+
+    /// 窗口里的最值，数据不足或者窗口里都是 NaN 时为 NaN。
+    fn value(&self) -> f64 {
+        if self.count >= self.length {
+            self.inner.front().map_or(f64::NAN, |v| v.1)
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+/// 增量计算 sma，参考 [`sma`]。
+#[derive(Debug, Clone)]
+pub struct SMA {
+    window: Window,
+    sum: f64,
+}
+
+impl SMA {
+    pub fn new(length: usize) -> Self {
+        Self {
+            window: Window::new(length),
+            sum: 0.0,
+        }
+    }
+}
+
+impl Indicator for SMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.sum += input - self.window.push(input).unwrap_or(0.0);
+        self.value()
+    }
+
+    fn value(&self) -> f64 {
+        if self.window.is_full() {
+            self.sum / self.window.length as f64
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+/// 增量计算 ema，参考 [`ema`]。
+#[derive(Debug, Clone)]
+pub struct EMA {
+    length: usize,
+    count: usize,
+    last: f64,
+}
+
+impl EMA {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            count: 0,
+            last: f64::NAN,
+        }
+    }
+}
+
+impl Indicator for EMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let alpha = 2.0 / (self.length + 1) as f64;
+
+        self.count += 1;
+        self.last = if self.last.is_nan() {
+            input
+        } else {
+            alpha * input + (1.0 - alpha) * self.last
+        };
+        self.value()
+    }
+
+    fn value(&self) -> f64 {
+        if self.count < self.length {
+            f64::NAN
+        } else {
+            self.last
+        }
+    }
+}
+
+/// 增量计算 rma，参考 [`rma`]。
+#[derive(Debug, Clone)]
+pub struct RMA {
+    length: usize,
+    count: usize,
+
+    /// 开始的 `length` 个值的和，用于计算第一个 rma。
+    sum: f64,
+
+    last: f64,
+}
+
+impl RMA {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            count: 0,
+            sum: 0.0,
+            last: f64::NAN,
+        }
+    }
+}
+
+impl Indicator for RMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let alpha = 1.0 / self.length as f64;
+
+        self.count += 1;
+
+        if self.count <= self.length {
+            self.sum += input;
+
+            if self.count == self.length {
+                self.last = self.sum / self.length as f64;
+            }
+        } else {
+            self.last = alpha * input + (1.0 - alpha) * self.last;
+        }
+
+        self.last
+    }
+
+    fn value(&self) -> f64 {
+        self.last
+    }
+}
+
+/// 增量计算 cci，参考 [`cci`]。
+#[derive(Debug, Clone)]
+pub struct CCI {
+    window: Window,
+    sma: SMA,
+    last: f64,
+}
+
+impl CCI {
+    pub fn new(length: usize) -> Self {
+        Self {
+            window: Window::new(length),
+            sma: SMA::new(length),
+            last: f64::NAN,
+        }
+    }
+}
+
+impl Indicator for CCI {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.window.push(input);
+        let ma = self.sma.update(input);
+
+        self.last = if self.window.is_full() {
+            let sum = self
+                .window
+                .inner
+                .iter()
+                .map(|v| (v - ma).abs())
+                .sum::<f64>();
+            (input - ma) / (0.015 * (sum / self.window.length as f64))
+        } else {
+            f64::NAN
+        };
+        self.last
+    }
+
+    fn value(&self) -> f64 {
+        self.last
+    }
+}
+
+/// 增量计算 macd，参考 [`macd`]。
+#[derive(Debug, Clone)]
+pub struct MACD {
+    short_ema: EMA,
+    long_ema: EMA,
+    dea_length: usize,
+
+    /// 数据足够时的最小数量。
+    length: usize,
+
+    count: usize,
+
+    /// dif 的 ema，从第一个有效的 dif 开始计算。
+    dea: f64,
+
+    last: (f64, f64, f64),
+}
+
+impl MACD {
+    pub fn new(short_length: usize, long_length: usize, dea_length: usize) -> Self {
+        Self {
+            short_ema: EMA::new(short_length),
+            long_ema: EMA::new(long_length),
+            dea_length,
+            length: short_length.max(long_length).max(dea_length),
+            count: 0,
+            dea: f64::NAN,
+            last: (f64::NAN, f64::NAN, f64::NAN),
+        }
+    }
+}
+
+impl Indicator for MACD {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64, f64) {
+        self.count += 1;
+
+        let dif = self.short_ema.update(input) - self.long_ema.update(input);
+
+        if !dif.is_nan() {
+            let alpha = 2.0 / (self.dea_length + 1) as f64;
+
+            self.dea = if self.dea.is_nan() {
+                dif
+            } else {
+                alpha * dif + (1.0 - alpha) * self.dea
+            };
+        }
+
+        self.last = if self.count < self.length {
+            (f64::NAN, f64::NAN, f64::NAN)
+        } else {
+            (dif, self.dea, (dif - self.dea) * 2.0)
+        };
+        self.last
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        self.last
+    }
+}
+
+/// 增量计算 rsi，参考 [`rsi`]。
+#[derive(Debug, Clone)]
+pub struct RSI {
+    prev: f64,
+    u_rma: RMA,
+    d_rma: RMA,
+    last: f64,
+}
+
+impl RSI {
+    pub fn new(length: usize) -> Self {
+        Self {
+            prev: f64::NAN,
+            u_rma: RMA::new(length),
+            d_rma: RMA::new(length),
+            last: f64::NAN,
+        }
+    }
+}
+
+impl Indicator for RSI {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let temp = input - self.prev;
+        let temp = if temp.is_nan() { 0.0 } else { temp };
+        self.prev = input;
+
+        let rs = self.u_rma.update(temp.max(0.0)) / self.d_rma.update((-temp).max(0.0));
+
+        self.last = 100.0 - 100.0 / (1.0 + rs);
+        self.last
+    }
+
+    fn value(&self) -> f64 {
+        self.last
+    }
+}
+
+/// 增量计算最高值，参考 [`highest`]。
+#[derive(Debug, Clone)]
+pub struct Highest {
+    deque: Monotonic,
+}
+
+impl Highest {
+    pub fn new(length: usize) -> Self {
+        Self {
+            deque: Monotonic::new(length),
+        }
+    }
+}
+
+impl Indicator for Highest {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.deque.push(input, |back| back >= input);
+        self.value()
+    }
+
+    fn value(&self) -> f64 {
+        self.deque.value()
+    }
+}
+
+/// 增量计算最低值，参考 [`lowest`]。
+#[derive(Debug, Clone)]
+pub struct Lowest {
+    deque: Monotonic,
+}
+
+impl Lowest {
+    pub fn new(length: usize) -> Self {
+        Self {
+            deque: Monotonic::new(length),
+        }
+    }
+}
+
+impl Indicator for Lowest {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.deque.push(input, |back| back <= input);
+        self.value()
+    }
+
+    fn value(&self) -> f64 {
+        self.deque.value()
+    }
+}
+
+/// 注册到 [`Context`] 的指标，参考 [`Context::register`]。
+pub struct IndicatorId<T> {
+    pub(crate) index: usize,
+    phantom: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for IndicatorId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for IndicatorId<T> {}
+
+impl<T> std::fmt::Debug for IndicatorId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IndicatorId").field(&self.index).finish()
+    }
+}
+
+/// 把 k 线输入类型擦除的指标。
+type Update = Box<dyn FnMut(&mut dyn std::any::Any, &K)>;
+
+/// 类型擦除的指标，以及从 k 线获取指标输入的函数。
+pub(crate) struct Registered {
+    key: String,
+    indicator: Box<dyn std::any::Any>,
+    update: Update,
+}
+
+impl Registered {
+    pub(crate) fn new<T, F>(key: &str, indicator: T, mut input: F) -> Self
+    where
+        T: Indicator + 'static,
+        F: FnMut(&K) -> T::Input + 'static,
+    {
+        Self {
+            key: key.to_string(),
+            indicator: Box::new(indicator),
+            update: Box::new(move |indicator, k| {
+                if let Some(v) = indicator.downcast_mut::<T>() {
+                    v.update(input(k));
+                }
+            }),
+        }
+    }
+
+    /// 输入新的 k 线。
+    pub(crate) fn update(&mut self, k: &K) {
+        (self.update)(self.indicator.as_mut(), k)
+    }
+}
+
+/// 已经注册的指标。
+#[derive(Default)]
+pub(crate) struct Registry {
+    inner: Vec<Registered>,
+}
+
+impl Registry {
+    /// 注册指标。
+    pub(crate) fn register<T, F>(&mut self, key: &str, indicator: T, input: F) -> IndicatorId<T>
+    where
+        T: Indicator + 'static,
+        F: FnMut(&K) -> T::Input + 'static,
+    {
+        self.inner.push(Registered::new(key, indicator, input));
+
+        IndicatorId {
+            index: self.inner.len() - 1,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// 按照名称查找已经注册的指标。
+    ///
+    /// * `return` 没有找到时为 None，名称相同但是类型不同时返回错误。
+    pub(crate) fn find<T>(&self, key: &str) -> anyhow::Result<Option<IndicatorId<T>>>
+    where
+        T: 'static,
+    {
+        match self.inner.iter().position(|v| v.key == key) {
+            Some(index) if self.inner[index].indicator.is::<T>() => Ok(Some(IndicatorId {
+                index,
+                phantom: std::marker::PhantomData,
+            })),
+            Some(_) => anyhow::bail!("indicator {}: registered with another type", key),
+            None => Ok(None),
+        }
+    }
+
+    /// 获取指标。
+    pub(crate) fn get<T>(&self, id: IndicatorId<T>) -> &T
+    where
+        T: 'static,
+    {
+        self.inner[id.index]
+            .indicator
+            .downcast_ref::<T>()
+            .expect("indicator type mismatch")
+    }
+
+    /// 最后一个注册的指标。
+    pub(crate) fn last_mut(&mut self) -> Option<&mut Registered> {
+        self.inner.last_mut()
+    }
+
+    /// 所有指标输入新的 k 线。
+    pub(crate) fn update(&mut self, k: &K) {
+        self.inner.iter_mut().for_each(|v| v.update(k));
+    }
+}
@@ -1,11 +1,33 @@
 mod backtester;
 mod base;
+mod benchmark;
+mod book;
 mod exchange;
+mod excursion;
+mod export;
+mod fee;
+mod indicator;
 mod match_engine;
+mod pattern;
+mod period;
+mod report;
+mod rule;
+mod sizer;
 mod util;
 
 pub use backtester::*;
 pub use base::*;
+pub use benchmark::*;
+pub use book::*;
 pub use exchange::*;
+pub use excursion::*;
+pub use export::*;
+pub use fee::*;
+pub use indicator::*;
 pub use match_engine::*;
+pub use pattern::*;
+pub use period::*;
+pub use report::*;
+pub use rule::*;
+pub use sizer::*;
 pub use util::*;
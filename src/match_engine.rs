@@ -1,7 +1,7 @@
 use crate::*;
 
 /// 信息。
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct Message {
     /// 最小委托数量。
     min_size: f64,
@@ -12,15 +12,44 @@ struct Message {
     /// K 线数据。
     k: K,
 
+    /// 当前 k 线的开始时间，用于统计持仓经过的 k 线数量，使用盘口或者逐笔成交撮合时为 1 分钟 k 线的开始时间。
+    #[serde(default)]
+    bar: u64,
+
     /// 委托 id，委托状态。
     delegate: Vec<(u64, DelegateState)>,
 
     /// 仓位。
     position: Option<Position>,
+
+    /// 风险档位，为空时使用 [`Config::maintenance`]。
+    tier: Vec<Tier>,
+
+    /// 是否为现货。
+    spot: bool,
+
+    /// 小级别 k 线，用于确定 k 线内部的价格路径。
+    lower: Vec<K>,
+
+    /// 盘口，存在时使用盘口撮合。
+    book: Option<OrderBook>,
+
+    /// 挂单的排队位置，委托 id，委托价格，排在前面的数量，小于 0 表示已经轮到成交。
+    queue: Vec<(u64, f64, f64)>,
+
+    /// 本次刷新需要成交的委托，委托 id，成交价格。
+    fill: Vec<(u64, f64)>,
+
+    /// 以市价委托的委托 id，使用盘口撮合时作为吃单处理。
+    taker: Vec<u64>,
+
+    /// 杠杆，插入产品时使用 [`Config::lever`]，现货为 1。
+    lever: u32,
 }
 
 /// 撮合引擎。
-#[derive(Debug)]
+/// 除了事件的订阅者，所有状态都可以序列化，用于保存和恢复回测或者模拟交易的进度。
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct MatchEngine {
     /// 余额。
     balance: f64,
@@ -36,6 +65,41 @@ pub struct MatchEngine {
 
     /// 历史仓位。
     history: Vec<Position>,
+
+    /// 现货资产，资产数量。
+    asset: Vec<(String, f64)>,
+
+    /// 现货资产的变化记录，时间，资产，变化后的资产数量。
+    asset_history: Vec<(u64, String, f64)>,
+
+    /// 还没有发送的事件。
+    event: Vec<Event>,
+
+    /// 事件的订阅者，不参与序列化。
+    #[serde(skip)]
+    subscriber: Vec<std::sync::mpsc::Sender<Event>>,
+
+    /// 委托记录。
+    order: Vec<OrderRecord>,
+
+    /// 手续费模型，为空时使用 [`Config::open_fee`] 和 [`Config::close_fee`]，不参与序列化。
+    #[serde(skip)]
+    fee: Option<Box<dyn Fee>>,
+
+    /// 是否设置了手续费模型，从快照恢复时需要重新设置手续费模型。
+    #[serde(default)]
+    fee_model: bool,
+
+    /// 设置了手续费模型时，开仓委托按照吃单预先扣除的手续费，委托 id，每单位数量的手续费。
+    #[serde(default)]
+    reserve: Vec<(u64, f64)>,
+
+    /// 过去 30 天的成交，时间，成交额。
+    volume: Vec<(u64, f64)>,
+
+    /// 拆分出来的委托 id，原委托 id。
+    #[serde(default)]
+    split: Vec<(u64, u64)>,
 }
 
 impl MatchEngine {
@@ -49,7 +113,360 @@ impl MatchEngine {
             config,
             product: Vec::new(),
             history: Vec::new(),
+            asset: Vec::new(),
+            asset_history: Vec::new(),
+            event: Vec::new(),
+            subscriber: Vec::new(),
+            order: Vec::new(),
+            fee: None,
+            fee_model: false,
+            reserve: Vec::new(),
+            volume: Vec::new(),
+            split: Vec::new(),
+        }
+    }
+
+    /// 设置手续费模型。
+    /// 设置后，手续费率由成交时是挂单还是吃单，以及过去 30 天的成交额决定。
+    /// 开仓委托按照吃单预先扣除估计的手续费，成交时按照实际的手续费退还或者补扣差额。
+    /// 存在委托时不能设置。
+    ///
+    /// * `fee` 手续费模型，例如 [`FeeSchedule`]。
+    pub fn set_fee<F>(&mut self, fee: F) -> anyhow::Result<()>
+    where
+        F: Fee + 'static,
+    {
+        if let Some((product, ..)) = self.product.iter().find(|v| !v.1.delegate.is_empty()) {
+            anyhow::bail!("product {}: can not set fee with delegate", product);
+        }
+
+        self.fee = Some(Box::new(fee));
+        self.fee_model = true;
+
+        Ok(())
+    }
+
+    /// 获取过去 30 天的成交额。
+    pub fn volume(&self) -> f64 {
+        let time = self.product.iter().map(|v| v.1.k.time).max().unwrap_or(0);
+        trailing_volume(&self.volume, time)
+    }
+
+    /// 订阅事件。
+    /// 委托，撤销委托，刷新之后，产生的事件会按顺序发送到接收端，接收端被丢弃后自动取消订阅。
+    pub fn subscribe(&mut self) -> std::sync::mpsc::Receiver<Event> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.subscriber.push(tx);
+        rx
+    }
+
+    /// 生成快照，包含余额，委托 id，交易配置，产品，委托，仓位和历史记录，不包含事件的订阅者。
+    ///
+    /// * `return` json 格式的快照。
+    pub fn snapshot(&self) -> anyhow::Result<String> {
+        serde_json::to_string(self).map_err(|e| anyhow::anyhow!("snapshot: {}", e))
+    }
+
+    /// 从快照恢复撮合引擎，恢复后需要重新订阅事件。
+    /// 快照设置了手续费模型时恢复失败，使用 [`MatchEngine::restore_fee`] 重新设置手续费模型。
+    ///
+    /// * `text` json 格式的快照。
+    pub fn restore<S>(text: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let result: Self =
+            serde_json::from_str(text.as_ref()).map_err(|e| anyhow::anyhow!("restore: {}", e))?;
+
+        if result.fee_model {
+            anyhow::bail!("restore: snapshot has a fee model, use restore_fee");
+        }
+
+        Ok(result)
+    }
+
+    /// 从快照恢复撮合引擎，同时重新设置快照中的手续费模型，恢复后需要重新订阅事件。
+    /// 快照没有设置手续费模型时恢复失败，使用 [`MatchEngine::restore`]。
+    ///
+    /// * `text` json 格式的快照。
+    /// * `fee` 手续费模型，需要和生成快照时的手续费模型相同。
+    pub fn restore_fee<S, F>(text: S, fee: F) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+        F: Fee + 'static,
+    {
+        let mut result: Self =
+            serde_json::from_str(text.as_ref()).map_err(|e| anyhow::anyhow!("restore: {}", e))?;
+
+        if !result.fee_model {
+            anyhow::bail!("restore: snapshot has no fee model, use restore");
+        }
+
+        result.fee = Some(Box::new(fee));
+
+        Ok(result)
+    }
+
+    /// 保存快照到文件。
+    ///
+    /// * `path` 文件路径。
+    pub fn save<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        std::fs::write(path, self.snapshot()?)
+            .map_err(|e| anyhow::anyhow!("write {}: {}", path.display(), e))
+    }
+
+    /// 从快照文件恢复撮合引擎，参考 [`MatchEngine::restore`]。
+    ///
+    /// * `path` 文件路径。
+    pub fn load<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("read {}: {}", path.display(), e))?;
+        Self::restore(text)
+    }
+
+    /// 从快照文件恢复撮合引擎，同时重新设置手续费模型，参考 [`MatchEngine::restore_fee`]。
+    ///
+    /// * `path` 文件路径。
+    /// * `fee` 手续费模型，需要和生成快照时的手续费模型相同。
+    pub fn load_fee<P, F>(path: P, fee: F) -> anyhow::Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+        F: Fee + 'static,
+    {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("read {}: {}", path.display(), e))?;
+        Self::restore_fee(text, fee)
+    }
+
+    fn flush(&mut self) {
+        // 委托已经不存在时，不再需要预先扣除的手续费
+        let product = &self.product;
+        self.reserve.retain(|v| {
+            product
+                .iter()
+                .any(|p| p.1.delegate.iter().any(|d| d.0 == v.0))
+        });
+
+        let event = std::mem::take(&mut self.event);
+
+        for v in event.iter() {
+            self.update_order(v);
+
+            if let Event::Fill { record, .. } = v {
+                self.volume.retain(|v| v.0 + VOLUME_PERIOD > record.time);
+                self.volume
+                    .push((record.time, record.price * record.quantity));
+            }
+        }
+
+        self.subscriber
+            .retain(|tx| event.iter().all(|v| tx.send(v.clone()).is_ok()));
+    }
+
+    /// 根据事件更新委托记录。
+    fn update_order(&mut self, event: &Event) {
+        let (id, time) = match event {
+            Event::Accept {
+                id,
+                product,
+                time,
+                delegate,
+            } => {
+                // 反手委托记录为开仓方向，数量包括平仓数量
+                let (v, close) = match delegate {
+                    DelegateState::Single(v)
+                    | DelegateState::OpenProfit(v, ..)
+                    | DelegateState::OpenLoss(v, ..)
+                    | DelegateState::OpenProfitLoss(v, ..)
+                    | DelegateState::ProfitLoss(v, ..) => (v, 0.0),
+                    DelegateState::Hedging(a, v)
+                    | DelegateState::HedgingProfit(a, v, ..)
+                    | DelegateState::HedgingLoss(a, v, ..)
+                    | DelegateState::HedgingProfitLoss(a, v, ..) => (v, a.quantity),
+                };
+
+                self.order.push(OrderRecord {
+                    id: *id,
+                    product: product.clone(),
+                    side: v.side,
+                    price: price_value(v.price),
+                    quantity: v.quantity + close,
+                    status: OrderStatus::New,
+                    fill_quantity: 0.0,
+                    fill_price: 0.0,
+                    fill_time: 0,
+                    stop_quantity: 0.0,
+                    stop_price: 0.0,
+                    stop_time: 0,
+                    time: *time,
+                    update_time: *time,
+                    reason: String::new(),
+                });
+
+                return;
+            }
+            Event::Reject {
+                product,
+                time,
+                reason,
+            } => {
+                self.order.push(OrderRecord {
+                    id: 0,
+                    product: product.clone(),
+                    side: Side::BuyLong,
+                    price: 0.0,
+                    quantity: 0.0,
+                    status: OrderStatus::Rejected,
+                    fill_quantity: 0.0,
+                    fill_price: 0.0,
+                    fill_time: 0,
+                    stop_quantity: 0.0,
+                    stop_price: 0.0,
+                    stop_time: 0,
+                    time: *time,
+                    update_time: *time,
+                    reason: reason.clone(),
+                });
+
+                return;
+            }
+            Event::Trigger { id, time, .. }
+            | Event::Amend { id, time, .. }
+            | Event::Cancel { id, time, .. }
+            | Event::Expire { id, time, .. } => (*id, *time),
+            Event::Fill { id, record, .. } => (*id, record.time),
+            _ => return,
+        };
+
+        // 拆分出来的委托记录到原委托
+        let id = self.split.iter().find(|v| v.0 == id).map_or(id, |v| v.1);
+
+        // 委托还没有成交的部分
+        let pending = self
+            .product
+            .iter()
+            .flat_map(|v| v.1.delegate.iter())
+            .find(|v| v.0 == id)
+            .and_then(|v| match v.1 {
+                DelegateState::Single(v) => Some(v),
+                DelegateState::OpenProfit(v, ..)
+                | DelegateState::OpenLoss(v, ..)
+                | DelegateState::OpenProfitLoss(v, ..)
+                | DelegateState::Hedging(.., v)
+                | DelegateState::HedgingProfit(_, v, ..)
+                | DelegateState::HedgingLoss(_, v, ..)
+                | DelegateState::HedgingProfitLoss(_, v, ..) => Some(v),
+                DelegateState::ProfitLoss(..) => None,
+            });
+
+        let record = match self.order.iter_mut().rev().find(|v| v.id == id) {
+            Some(v) => v,
+            None => return,
+        };
+
+        // 全部成交后还在委托数量以内的成交，例如同一次刷新中反手委托的开仓部分，仍然是委托的成交
+        // 超出委托数量的成交是止盈止损的成交
+        let done = match (record.status, event) {
+            (OrderStatus::Filled, Event::Fill { record: fill, .. })
+                if record.fill_quantity + fill.quantity > record.quantity * (1.0 + 1e-9) =>
+            {
+                record.stop_price = (record.stop_price * record.stop_quantity
+                    + fill.price * fill.quantity)
+                    / (record.stop_quantity + fill.quantity);
+                record.stop_quantity += fill.quantity;
+                record.stop_time = fill.time;
+                record.update_time = time;
+                true
+            }
+            (OrderStatus::Filled, Event::Fill { .. }) => false,
+            (OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired, _) => true,
+            _ => false,
+        };
+
+        if done {
+            return;
         }
+
+        record.update_time = time;
+
+        match event {
+            Event::Trigger { .. } => record.status = OrderStatus::Triggered,
+            Event::Amend {
+                delegate:
+                    DelegateState::Single(v)
+                    | DelegateState::OpenProfit(v, ..)
+                    | DelegateState::OpenLoss(v, ..)
+                    | DelegateState::OpenProfitLoss(v, ..),
+                ..
+            } => {
+                record.price = price_value(v.price);
+                record.quantity = v.quantity;
+            }
+            Event::Cancel { .. } => record.status = OrderStatus::Cancelled,
+            Event::Expire { .. } => record.status = OrderStatus::Expired,
+            Event::Fill { record: fill, .. } => {
+                record.fill_price = (record.fill_price * record.fill_quantity
+                    + fill.price * fill.quantity)
+                    / (record.fill_quantity + fill.quantity);
+                record.fill_quantity += fill.quantity;
+                record.fill_time = fill.time;
+
+                // 委托方向的部分仍然存在，则为部分成交
+                record.status = match pending {
+                    Some(v) if v.side == record.side => OrderStatus::PartiallyFilled,
+                    _ => OrderStatus::Filled,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// 获取委托记录。
+    ///
+    /// * `id` 委托 id。
+    pub fn order_record(&self, id: u64) -> Option<&OrderRecord> {
+        if id == 0 {
+            return None;
+        }
+
+        self.order.iter().rev().find(|v| v.id == id)
+    }
+
+    /// 获取所有的委托记录，包括委托失败的记录，委托失败的记录 id 为 0。
+    pub fn order_records(&self) -> &Vec<OrderRecord> {
+        &self.order
+    }
+
+    /// 获取还在工作的委托，即等待成交，已经触发和部分成交的委托。
+    /// 全部成交后还没有触发的止盈止损委托不在其中，使用 [`MatchEngine::delegate`] 获取。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    pub fn open_orders<S>(&self, product: S) -> Vec<&OrderRecord>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+        self.product
+            .iter()
+            .filter(|v| v.0 == product)
+            .flat_map(|v| v.1.delegate.iter())
+            .filter_map(|v| self.order_record(v.0))
+            .filter(|v| {
+                matches!(
+                    v.status,
+                    OrderStatus::New | OrderStatus::Triggered | OrderStatus::PartiallyFilled
+                )
+            })
+            .collect()
     }
 
     /// 获取余额。
@@ -92,6 +509,34 @@ impl MatchEngine {
         &self.history
     }
 
+    /// 获取现货资产数量。
+    /// 计价货币即余额，使用 [`MatchEngine::balance`] 获取。
+    ///
+    /// * `asset` 资产，例如 BTC。
+    pub fn asset<S>(&self, asset: S) -> f64
+    where
+        S: AsRef<str>,
+    {
+        let asset = asset.as_ref();
+        self.asset
+            .iter()
+            .find(|v| v.0 == asset)
+            .map(|v| v.1)
+            .unwrap_or(0.0)
+    }
+
+    /// 获取所有现货资产。
+    pub fn assets(&self) -> &Vec<(String, f64)> {
+        &self.asset
+    }
+
+    /// 获取现货资产的变化记录。
+    ///
+    /// * `return` 时间，资产，变化后的资产数量。
+    pub fn asset_history(&self) -> &Vec<(u64, String, f64)> {
+        &self.asset_history
+    }
+
     /// 插入产品。
     ///
     /// * `product` 交易产品。
@@ -101,8 +546,24 @@ impl MatchEngine {
     where
         S: AsRef<str>,
     {
-        let product = product.as_ref();
+        self.insert(product.as_ref(), min_size, min_notional, false);
+    }
+
+    /// 插入现货产品。
+    /// 现货不能做空，没有杠杆，使用计价货币买入基础货币，开仓手续费从收到的基础货币中扣除，平仓手续费从收到的计价货币中扣除。
+    /// 计价货币即余额，基础货币使用 [`MatchEngine::asset`] 获取。
+    ///
+    /// * `product` 交易产品，例如 BTC-USDT。
+    /// * `min_size` 最小委托数量。
+    /// * `min_notional` 最小名义价值。
+    pub fn insert_spot_product<S>(&mut self, product: S, min_size: f64, min_notional: f64)
+    where
+        S: AsRef<str>,
+    {
+        self.insert(product.as_ref(), min_size, min_notional, true);
+    }
 
+    fn insert(&mut self, product: &str, min_size: f64, min_notional: f64, spot: bool) {
         let message = Message {
             min_size,
             min_notional,
@@ -113,8 +574,17 @@ impl MatchEngine {
                 low: 0.0,
                 close: 0.0,
             },
+            bar: 0,
             delegate: Vec::new(),
             position: None,
+            tier: Vec::new(),
+            spot,
+            lower: Vec::new(),
+            book: None,
+            queue: Vec::new(),
+            fill: Vec::new(),
+            taker: Vec::new(),
+            lever: if spot { 1 } else { self.config.lever },
         };
 
         if let Some(v) = self.product.iter().position(|v| v.0 == product) {
@@ -135,6 +605,165 @@ impl MatchEngine {
         self.product.retain(|v| v.0 != product);
     }
 
+    /// 设置风险档位。
+    /// 设置后，强平价格使用仓位价值所在档位的维持保证金率和速算额计算，
+    /// 杠杆超过档位的最大杠杆或者仓位价值超过最后一个档位的委托将被拒绝。
+    ///
+    /// * `product` 交易产品。
+    /// * `tier` 按最大名义价值从小到大排列的档位，空数组表示使用 [`Config::maintenance`]。
+    pub fn set_tier<S>(&mut self, product: S, tier: Vec<Tier>) -> anyhow::Result<()>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+
+        for i in 1..tier.len() {
+            if tier[i].max_notional <= tier[i - 1].max_notional {
+                anyhow::bail!(
+                    "product {}: tier max notional must be ascending: {} <= {}",
+                    product,
+                    tier[i].max_notional,
+                    tier[i - 1].max_notional
+                );
+            }
+        }
+
+        self.product
+            .iter_mut()
+            .find(|v| v.0 == product)
+            .map(|v| &mut v.1)
+            .ok_or(anyhow::anyhow!("no product: {}", product))?
+            .tier = tier;
+
+        Ok(())
+    }
+
+    /// 获取产品的杠杆。
+    ///
+    /// * `product` 交易产品。
+    pub fn lever<S>(&self, product: S) -> Option<u32>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+        self.product
+            .iter()
+            .find(|v| v.0 == product)
+            .map(|v| v.1.lever)
+    }
+
+    /// 设置产品的杠杆，之后的委托使用这个杠杆计算保证金。
+    /// 存在仓位时，仓位的保证金必须足够维持新杠杆下的仓位价值，调整后重新计算强平价格，强平价格不能越过当前价格。
+    /// 已经存在的委托的保证金不变。
+    ///
+    /// 杠杆只能按照产品设置，不能按照委托设置，因为同一个产品只有一个仓位，仓位的强平价格和维持保证金只能使用一个杠杆计算。
+    /// 单个委托需要更低的杠杆时，使用委托的 `margin` 参数指定保证金，例如 价格 × 数量 ÷ 杠杆，多出的部分作为追加保证金。
+    ///
+    /// * `product` 交易产品。
+    /// * `lever` 杠杆。
+    pub fn set_lever<S>(&mut self, product: S, lever: u32) -> anyhow::Result<()>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+
+        let Message {
+            k,
+            position,
+            tier,
+            spot,
+            lever: current_lever,
+            ..
+        } = self
+            .product
+            .iter_mut()
+            .find(|v| v.0 == product)
+            .map(|v| &mut v.1)
+            .ok_or(anyhow::anyhow!("no product: {}", product))?;
+
+        if *spot {
+            anyhow::bail!("product {}: spot product does not support lever", product);
+        }
+
+        if lever == 0 {
+            anyhow::bail!("product {}: lever invalid: {}", product, lever);
+        }
+
+        // 检查风险档位
+        if !tier.is_empty() {
+            let notional = position
+                .as_ref()
+                .map(|v| v.open_price * v.quantity)
+                .unwrap_or(0.0);
+
+            let current_tier = tier
+                .iter()
+                .find(|v| notional <= v.max_notional)
+                .unwrap_or(tier.last().unwrap());
+
+            if lever > current_tier.max_lever {
+                anyhow::bail!(
+                    "product {}: lever > tier max lever: {} > {}",
+                    product,
+                    lever,
+                    current_tier.max_lever
+                );
+            }
+        }
+
+        if let Some(current_position) = position {
+            // 保证金必须足够维持仓位价值
+            let min_margin = current_position.open_price * current_position.quantity / lever as f64;
+
+            if current_position.margin < min_margin {
+                anyhow::bail!(
+                    "product {}: position margin < open quantity value / lever: {} < {}",
+                    product,
+                    current_position.margin,
+                    min_margin
+                );
+            }
+
+            let rate = liquidation_fee(
+                &self.config,
+                &self.fee,
+                &self.volume,
+                product,
+                current_position.side,
+                k.time,
+            );
+
+            let liquidation_price = liquidation_price(
+                &self.config,
+                tier,
+                lever,
+                current_position.side,
+                current_position.open_price,
+                current_position.quantity,
+                current_position.margin,
+                rate,
+            );
+
+            if current_position.side == Side::BuyLong && liquidation_price >= k.close
+                || current_position.side == Side::SellShort && liquidation_price <= k.close
+            {
+                anyhow::bail!(
+                    "product {}: liquidation price crosses market price: {} {}",
+                    product,
+                    liquidation_price,
+                    k.close
+                );
+            }
+
+            current_position.lever = lever;
+            current_position.liquidation_price = liquidation_price;
+        }
+
+        *current_lever = lever;
+
+        Ok(())
+    }
+
     /// 准备。
     /// 在调用委托之前，需要准备。
     /// 在准备之前，需要插入产品。
@@ -145,13 +774,124 @@ impl MatchEngine {
     where
         S: AsRef<str>,
     {
-        let product = product.as_ref();
+        self.ready_lower(product, k, Vec::new());
+    }
+
+    /// 准备，同时提供小级别 k 线。
+    /// 在 [`PricePath::Lower`] 下使用小级别 k 线确定 k 线内部的价格路径。
+    ///
+    /// * `product` 交易产品。
+    /// * `k` k 线数据。
+    /// * `lower` 这根 k 线时间范围之内的小级别 k 线数据，按时间从旧到新排列。
+    pub fn ready_lower<S>(&mut self, product: S, k: K, lower: Vec<K>)
+    where
+        S: AsRef<str>,
+    {
+        let message = self.message_mut(product.as_ref());
+        message.bar = k.time;
+        message.k = k;
+        message.lower = lower;
+    }
+
+    fn message_mut(&mut self, product: &str) -> &mut Message {
         self.product
             .iter_mut()
             .find(|v| v.0 == product)
             .map(|v| &mut v.1)
             .expect(&format!("no product: {}", product))
-            .k = k;
+    }
+
+    /// 准备，使用盘口数据。
+    /// 第一次调用后这个产品使用盘口撮合：
+    /// 挂单按照价格档位的数量估计排队位置，成交价格穿过委托价格，或者排在前面的数量被成交完，或者对手盘价格穿过委托价格时才会成交；
+    /// 吃单依次吃掉对手盘的价格档位，按照成交均价成交，回放的盘口不会因为吃单而改变。
+    /// 挂单是指委托价格优于触发价的一侧，例如低于市价的做多限价委托，其他委托都是吃单，包括市价委托。
+    /// 盘口数量减少时，认为撤单发生在排队位置的前面。
+    ///
+    /// * `product` 交易产品。
+    /// * `update` 盘口快照或者增量。
+    pub fn ready_book<S>(&mut self, product: S, update: &BookUpdate)
+    where
+        S: AsRef<str>,
+    {
+        let message = self.message_mut(product.as_ref());
+
+        let book = message.book.get_or_insert_with(OrderBook::new);
+        book.apply(update);
+        message.k.time = update.time;
+        message.bar = update.time - update.time % 60_000;
+
+        // 刚开始没有成交价格时使用中间价
+        if message.k.close == 0.0 {
+            if let Some(v) = book.mid() {
+                message.k = K {
+                    time: update.time,
+                    open: v,
+                    high: v,
+                    low: v,
+                    close: v,
+                };
+            }
+        }
+
+        for (id, delegate_state) in message.delegate.iter_mut() {
+            for delegate in active_delegate(delegate_state) {
+                if let Some((buy, price)) = passive(delegate, &message.taker, *id) {
+                    let size = book.size(buy, price);
+                    let queue = queue_mut(&mut message.queue, book, *id, buy, price);
+                    *queue = queue.min(size);
+                }
+            }
+        }
+    }
+
+    /// 准备，使用逐笔成交。
+    /// 使用盘口撮合时，成交会消耗挂单前面的排队数量。
+    ///
+    /// * `product` 交易产品。
+    /// * `trade` 逐笔成交。
+    pub fn ready_trade<S>(&mut self, product: S, trade: &Trade)
+    where
+        S: AsRef<str>,
+    {
+        let message = self.message_mut(product.as_ref());
+
+        message.k = K {
+            time: trade.time,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+        };
+        message.bar = trade.time - trade.time % 60_000;
+        message.lower.clear();
+
+        let book = match message.book {
+            Some(ref v) => v,
+            None => return,
+        };
+
+        for (id, delegate_state) in message.delegate.iter_mut() {
+            for delegate in active_delegate(delegate_state) {
+                if let Some((buy, price)) = passive(delegate, &message.taker, *id) {
+                    let queue = queue_mut(&mut message.queue, book, *id, buy, price);
+
+                    if buy && trade.price < price || !buy && trade.price > price {
+                        // 成交价格穿过委托价格
+                        *queue = -1.0;
+                    } else if trade.price == price
+                        && (buy && trade.side == TradeSide::Sell
+                            || !buy && trade.side == TradeSide::Buy)
+                    {
+                        *queue -= trade.size;
+
+                        if *queue == 0.0 {
+                            *queue = -1.0;
+                        }
+                    }
+                }
+            }
+        }
     }
 
     /// 委托。
@@ -164,7 +904,7 @@ impl MatchEngine {
     /// 做多的止损触发价不能大于等于委托价格。
     /// 做空的止损触发价不能小于等于委托价格。
     /// 限价平仓委托不会在当前 k 线被成交。
-    /// 平仓不会导致仓位反向开单，平仓数量只能小于等于现有持仓数量。
+    /// 平仓不会导致仓位反向开单，平仓数量只能小于等于现有持仓数量，需要反手时使用 [`MatchEngine::reverse`]。
     /// 如果在进行平仓操作后，现有的限价平仓委托的平仓量小于持仓量，则该委托将被撤销。
     /// 平仓的止盈止损无效。
     ///
@@ -180,7 +920,304 @@ impl MatchEngine {
     /// * `return` 委托 id。
     pub fn order<S>(
         &mut self,
-        product: S,
+        product: S,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+
+        let result = self.order_inner(
+            product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        );
+
+        self.accept(product, result)
+    }
+
+    /// 只减仓委托。
+    /// 按照买卖方向减少反向的仓位，不会增加仓位，也不会反向开仓，没有反向的仓位时委托失败。
+    /// 委托数量超过持仓数量时按照持仓数量委托，成交前持仓数量小于委托数量时委托失效。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `side` 委托方向，[`Side::BuyLong`] 和 [`Side::SellLong`] 表示买入减少空仓，[`Side::SellShort`] 和 [`Side::BuySell`] 表示卖出减少多仓。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，单位为币，[`Unit::Ignore`] 表示全部仓位，[`Unit::Proportion`] 表示占用仓位的比例。
+    /// * `return` 委托 id。
+    pub fn reduce_only<S>(
+        &mut self,
+        product: S,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+    ) -> anyhow::Result<u64>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+        let result = self.reduce_only_inner(product, side, price, quantity);
+        self.accept(product, result)
+    }
+
+    fn reduce_only_inner(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+    ) -> anyhow::Result<u64> {
+        let Message {
+            min_size, position, ..
+        } = self
+            .product
+            .iter()
+            .find(|v| v.0 == product)
+            .map(|v| &v.1)
+            .ok_or(anyhow::anyhow!("no product: {}", product))?;
+
+        let side = match side {
+            Side::BuyLong | Side::SellLong => Side::SellLong,
+            Side::SellShort | Side::BuySell => Side::BuySell,
+        };
+
+        let position = match position {
+            Some(v) => v,
+            None => anyhow::bail!("product {}: reduce only, but no position", product),
+        };
+
+        // 只能减少反向的仓位
+        if side == Side::SellLong && position.side != Side::SellShort
+            || side == Side::BuySell && position.side != Side::BuyLong
+        {
+            anyhow::bail!(
+                "product {}: reduce only {:?}, but position side is {:?}",
+                product,
+                side,
+                position.side
+            );
+        }
+
+        let quantity = match quantity {
+            Unit::Ignore => position.quantity,
+            Unit::Quantity(v) => v.min(position.quantity),
+            Unit::Proportion(v) => (position.quantity * v / min_size).floor() * min_size,
+        };
+
+        // 按照比例向下取整后可能为 0
+        if quantity <= 0.0 || quantity < *min_size {
+            anyhow::bail!(
+                "product {}: reduce only quantity < min size: {} < {}",
+                product,
+                quantity,
+                min_size
+            );
+        }
+
+        self.order_inner(
+            product,
+            side,
+            price,
+            Unit::Quantity(quantity),
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+    }
+
+    /// 反手委托。
+    /// 成交时先平掉全部反向的仓位，再按照委托数量开仓，平仓和开仓使用相同的成交价格。
+    /// 没有仓位时等同于 [`MatchEngine::order`]，存在同方向的仓位时委托失败。
+    /// 成交前仓位被平仓或者减仓时，平仓部分失效，只开仓。
+    /// 检查余额时计入平仓部分释放的仓位保证金，所以成交前余额可能小于 0。
+    ///
+    /// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    /// * `side` 开仓方向，[`Side::BuyLong`] 或者 [`Side::SellShort`]。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 开仓数量，单位为币，[`Unit::Ignore`] 表示使用 [`Config::quantity`] 的设置。
+    /// * `margin` 开仓保证金，[`Unit::Ignore`] 表示使用 [`Config::margin`] 的设置。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_profit` 无效。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不设置，且 `stop_loss` 无效。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `stop_loss` 止损委托格，[`Unit::Ignore`] 表示不设置，其他表示限价。
+    /// * `return` 委托 id。
+    #[allow(clippy::too_many_arguments)]
+    pub fn reverse<S>(
+        &mut self,
+        product: S,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+
+        let result = self.reverse_inner(
+            product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        );
+
+        self.accept(product, result)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reverse_inner(
+        &mut self,
+        product: &str,
+        side: Side,
+        price: f64,
+        quantity: Unit,
+        margin: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<u64> {
+        if side != Side::BuyLong && side != Side::SellShort {
+            anyhow::bail!("product {}: reverse side must be open: {:?}", product, side);
+        }
+
+        let position = self
+            .product
+            .iter()
+            .find(|v| v.0 == product)
+            .ok_or(anyhow::anyhow!("no product: {}", product))?
+            .1
+            .position
+            .as_ref();
+
+        // 平掉全部仓位的委托，委托价格和开仓委托相同
+        let close = match position {
+            Some(v) if v.side == side => anyhow::bail!(
+                "product {}: reverse {:?}, but position side is {:?}",
+                product,
+                side,
+                v.side
+            ),
+            Some(v) => Some(Delegate {
+                side: if v.side == Side::BuyLong {
+                    Side::BuySell
+                } else {
+                    Side::SellLong
+                },
+                price: Price::GreaterThanMarket(0.0),
+                quantity: v.quantity,
+                margin: v.margin,
+                append_margin: 0.0,
+            }),
+            None => None,
+        };
+
+        // 平仓部分成交后会释放仓位的保证金，检查余额时计入
+        let release = close.map_or(0.0, |v| v.margin);
+
+        self.balance += release;
+
+        let result = self.order_inner(
+            product,
+            side,
+            price,
+            quantity,
+            margin,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        );
+
+        self.balance -= release;
+
+        let id = result?;
+
+        if let Some(mut close) = close {
+            let delegate_state = &mut self.message_mut(product).delegate.last_mut().unwrap().1;
+
+            *delegate_state = match *delegate_state {
+                DelegateState::Single(a) => {
+                    close.price = a.price;
+                    DelegateState::Hedging(close, a)
+                }
+                DelegateState::OpenProfit(a, b) => {
+                    close.price = a.price;
+                    DelegateState::HedgingProfit(close, a, b)
+                }
+                DelegateState::OpenLoss(a, b) => {
+                    close.price = a.price;
+                    DelegateState::HedgingLoss(close, a, b)
+                }
+                DelegateState::OpenProfitLoss(a, b, c) => {
+                    close.price = a.price;
+                    DelegateState::HedgingProfitLoss(close, a, b, c)
+                }
+                v => v,
+            };
+        }
+
+        Ok(id)
+    }
+
+    /// 记录委托的结果并且发送事件。
+    fn accept(&mut self, product: &str, result: anyhow::Result<u64>) -> anyhow::Result<u64> {
+        let time = self
+            .product
+            .iter()
+            .find(|v| v.0 == product)
+            .map(|v| v.1.k.time)
+            .unwrap_or(0);
+
+        self.event.push(match result {
+            Ok(id) => Event::Accept {
+                id,
+                product: product.to_string(),
+                time,
+                delegate: self.delegate(id).unwrap(),
+            },
+            Err(ref e) => Event::Reject {
+                product: product.to_string(),
+                time,
+                reason: e.to_string(),
+            },
+        });
+
+        self.flush();
+
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn order_inner(
+        &mut self,
+        product: &str,
         side: Side,
         price: f64,
         quantity: Unit,
@@ -189,18 +1226,18 @@ impl MatchEngine {
         stop_loss_condition: Unit,
         stop_profit: Unit,
         stop_loss: Unit,
-    ) -> anyhow::Result<u64>
-    where
-        S: AsRef<str>,
-    {
-        let product = product.as_ref();
-
+    ) -> anyhow::Result<u64> {
         let Message {
             min_size,
             min_notional,
             k,
             delegate,
             position,
+            tier,
+            spot,
+            taker,
+            lever,
+            ..
         } = self
             .product
             .iter_mut()
@@ -208,9 +1245,25 @@ impl MatchEngine {
             .map(|v| &mut v.1)
             .ok_or(anyhow::anyhow!("no product: {}", product))?;
 
+        // 现货只能买入和卖出
+        if *spot && (side == Side::SellShort || side == Side::SellLong) {
+            anyhow::bail!(
+                "product {}: spot product does not support {:?}",
+                product,
+                side
+            );
+        }
+
+        if *spot && margin != Unit::Ignore {
+            anyhow::bail!("product {}: spot product does not support margin", product);
+        }
+
+        // 价格为 0 的是市价委托，以市价委托的是吃单
+        let market = price == 0.0;
+
         if side == Side::BuyLong || side == Side::SellShort {
             // 市价转换
-            let price = if price == 0.0 { k.close } else { price };
+            let price = if market { k.close } else { price };
 
             // 委托数量
             let quantity = match if quantity == Unit::Ignore {
@@ -248,31 +1301,76 @@ impl MatchEngine {
                 );
             }
 
-            // 投入的保证金
-            let margin = match if margin == Unit::Ignore {
+            // 投入的保证金，现货没有杠杆，即成交额
+            let margin = match if *spot {
+                Unit::Quantity(quantity_value)
+            } else if margin == Unit::Ignore {
                 self.config.margin
             } else {
                 margin
             } {
-                Unit::Ignore => price * quantity / self.config.lever as f64,
+                Unit::Ignore => price * quantity / *lever as f64,
                 Unit::Quantity(v) => v,
                 Unit::Proportion(v) => self.config.initial_margin * v,
             };
 
             // 保证金必须足够维持仓位价值
             // 写成乘法会有精度问题
-            if margin < quantity_value / self.config.lever as f64 {
+            if margin < quantity_value / *lever as f64 {
                 anyhow::bail!(
                     "product {}: margin * lever < open quantity value: {} * {} < {}",
                     product,
                     margin,
-                    self.config.lever,
+                    lever,
                     quantity_value
                 );
             }
 
-            // 手续费
-            let fee = price * quantity * self.config.open_fee;
+            // 检查风险档位
+            if !tier.is_empty() {
+                // 成交后的仓位价值
+                let notional = match position {
+                    Some(v) if v.side == side => v.open_price * v.quantity + quantity_value,
+                    _ => quantity_value,
+                };
+
+                let current_tier =
+                    tier.iter()
+                        .find(|v| notional <= v.max_notional)
+                        .ok_or(anyhow::anyhow!(
+                            "product {}: position value > max tier notional: {} > {}",
+                            product,
+                            notional,
+                            tier.last().unwrap().max_notional
+                        ))?;
+
+                if *lever > current_tier.max_lever {
+                    anyhow::bail!(
+                        "product {}: lever > tier max lever: {} > {}",
+                        product,
+                        lever,
+                        current_tier.max_lever
+                    );
+                }
+            }
+
+            // 手续费，现货的手续费在成交时从收到的基础货币中扣除
+            // 设置了手续费模型时，按照吃单估计手续费，在成交时结算差额
+            let fee = if *spot {
+                0.0
+            } else {
+                price
+                    * quantity
+                    * fee_rate(
+                        &self.config,
+                        &self.fee,
+                        &self.volume,
+                        product,
+                        side,
+                        false,
+                        k.time,
+                    )
+            };
 
             // 检查余额
             if self.balance < margin + fee {
@@ -432,11 +1530,7 @@ impl MatchEngine {
                 }
             };
 
-            let price = if price >= k.close {
-                Price::GreaterThanMarket(price)
-            } else {
-                Price::LessThanMarket(price)
-            };
+            let price = delegate_price(side, price, k.close, market);
 
             let ds = match (stop_profit_condition, stop_loss_condition) {
                 (Unit::Quantity(a), Unit::Ignore) => DelegateState::OpenProfit(
@@ -563,6 +1657,14 @@ impl MatchEngine {
 
             self.id += 1;
 
+            if self.fee.is_some() && fee != 0.0 {
+                self.reserve.push((self.id, fee / quantity));
+            }
+
+            if market {
+                taker.push(self.id);
+            }
+
             delegate.push((self.id, ds));
 
             return Ok(self.id);
@@ -583,7 +1685,7 @@ impl MatchEngine {
                 );
             }
 
-            let price = if price == 0.0 { k.close } else { price };
+            let price = if market { k.close } else { price };
 
             // 委托数量
             let quantity = match quantity {
@@ -629,15 +1731,15 @@ impl MatchEngine {
 
             self.id += 1;
 
+            if market {
+                taker.push(self.id);
+            }
+
             delegate.push((
                 self.id,
                 DelegateState::Single(Delegate {
                     side,
-                    price: if price >= k.close {
-                        Price::GreaterThanMarket(price)
-                    } else {
-                        Price::LessThanMarket(price)
-                    },
+                    price: delegate_price(side, price, k.close, market),
                     quantity,
                     margin: quantity / position.quantity * position.margin,
                     append_margin: 0.0,
@@ -650,29 +1752,601 @@ impl MatchEngine {
         anyhow::bail!("no position: {}", product);
     }
 
+    /// 调整逐仓仓位的保证金。
+    /// 增加的保证金从余额中扣除，减少的保证金返还到余额，调整后重新计算强平价格，还没有成交的平仓委托的保证金按照比例调整。
+    /// 减少保证金后，保证金不能小于仓位价值除以杠杆，且强平价格不能越过当前价格。
+    ///
+    /// * `product` 交易产品，例如，合约 BTC-USDT-SWAP。
+    /// * `margin` 调整的数量，正数表示增加，负数表示减少。
+    pub fn adjust_margin<S>(&mut self, product: S, margin: f64) -> anyhow::Result<()>
+    where
+        S: AsRef<str>,
+    {
+        let product = product.as_ref();
+
+        let Message {
+            k,
+            delegate,
+            position,
+            tier,
+            spot,
+            lever,
+            ..
+        } = self
+            .product
+            .iter_mut()
+            .find(|v| v.0 == product)
+            .map(|v| &mut v.1)
+            .ok_or(anyhow::anyhow!("no product: {}", product))?;
+
+        if *spot {
+            anyhow::bail!("product {}: spot product does not support margin", product);
+        }
+
+        let current_position = position
+            .as_mut()
+            .ok_or(anyhow::anyhow!("no position: {}", product))?;
+
+        if margin == 0.0 || !margin.is_finite() {
+            anyhow::bail!("product {}: margin invalid: {}", product, margin);
+        }
+
+        if margin > self.balance {
+            anyhow::bail!(
+                "product {}: insufficient fund: balance < margin: {} < {}",
+                product,
+                self.balance,
+                margin
+            );
+        }
+
+        let new_margin = current_position.margin + margin;
+
+        // 保证金必须足够维持仓位价值
+        let min_margin = current_position.open_price * current_position.quantity / *lever as f64;
+
+        if margin < 0.0 && new_margin < min_margin {
+            anyhow::bail!(
+                "product {}: position margin < open quantity value / lever: {} < {}",
+                product,
+                new_margin,
+                min_margin
+            );
+        }
+
+        let rate = liquidation_fee(
+            &self.config,
+            &self.fee,
+            &self.volume,
+            product,
+            current_position.side,
+            k.time,
+        );
+
+        let liquidation_price = liquidation_price(
+            &self.config,
+            tier,
+            *lever,
+            current_position.side,
+            current_position.open_price,
+            current_position.quantity,
+            new_margin,
+            rate,
+        );
+
+        if margin < 0.0
+            && (current_position.side == Side::BuyLong && liquidation_price >= k.close
+                || current_position.side == Side::SellShort && liquidation_price <= k.close)
+        {
+            anyhow::bail!(
+                "product {}: liquidation price crosses market price: {} {}",
+                product,
+                liquidation_price,
+                k.close
+            );
+        }
+
+        // 平仓委托返还的保证金按照比例调整
+        let ratio = new_margin / current_position.margin;
+
+        for (.., delegate_state) in delegate.iter_mut() {
+            let close = match delegate_state {
+                DelegateState::Single(v) if v.side == Side::BuySell || v.side == Side::SellLong => {
+                    vec![v]
+                }
+                DelegateState::Hedging(v, ..)
+                | DelegateState::HedgingProfit(v, ..)
+                | DelegateState::HedgingLoss(v, ..)
+                | DelegateState::HedgingProfitLoss(v, ..) => vec![v],
+                DelegateState::ProfitLoss(a, b) => vec![a, b],
+                _ => Vec::new(),
+            };
+
+            for v in close {
+                v.margin *= ratio;
+            }
+        }
+
+        self.balance -= margin;
+        current_position.margin = new_margin;
+        current_position.liquidation_price = liquidation_price;
+
+        self.event.push(Event::Margin {
+            time: k.time,
+            position: current_position.clone(),
+        });
+
+        self.flush();
+
+        Ok(())
+    }
+
     /// 取消委托。
     ///
     /// * `id` 委托 id。
     pub fn cancel(&mut self, id: u64) -> bool {
+        let result = self.cancel_inner(id);
+        self.flush();
+        result
+    }
+
+    /// 修改委托。
+    /// 委托 id 不变，修改后的委托和 [`MatchEngine::order`] 使用相同的检查规则，检查失败时委托保持不变，修改后的委托失去原来的排队位置。
+    /// 还没有成交的开仓委托，平仓委托和反手委托，保证金按照原委托的保证金和仓位价值的比例重新计算，反手委托的平仓部分使用新的委托价格。
+    /// 成交后的止盈止损委托，包括市价和限价的止盈止损，`price` 必须为 0，使用止盈止损参数修改触发价格和委托价格，触发价格不能已经被当前价格触发，`quantity` 同时修改止盈和止损的数量。
+    /// 平仓委托没有止盈止损，修改时止盈止损参数必须为 [`Unit::Ignore`]。
+    /// 没有修改的字段保持不变，包括追加保证金。
+    ///
+    /// * `id` 委托 id。
+    /// * `price` 委托价格，0 表示市价，其他表示限价。
+    /// * `quantity` 委托数量，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit_condition` 止盈触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss_condition` 止损触发价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_profit` 止盈委托价格，[`Unit::Ignore`] 表示不修改。
+    /// * `stop_loss` 止损委托价格，[`Unit::Ignore`] 表示不修改。
+    #[allow(clippy::too_many_arguments)]
+    pub fn amend(
+        &mut self,
+        id: u64,
+        price: f64,
+        quantity: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<()> {
+        let result = self.amend_inner(
+            id,
+            price,
+            quantity,
+            stop_profit_condition,
+            stop_loss_condition,
+            stop_profit,
+            stop_loss,
+        );
+        self.flush();
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn amend_inner(
+        &mut self,
+        id: u64,
+        price: f64,
+        quantity: Unit,
+        stop_profit_condition: Unit,
+        stop_loss_condition: Unit,
+        stop_profit: Unit,
+        stop_loss: Unit,
+    ) -> anyhow::Result<()> {
+        let (product_index, delegate_index) = self
+            .product
+            .iter()
+            .enumerate()
+            .find_map(|(i, v)| v.1.delegate.iter().position(|v| v.0 == id).map(|v| (i, v)))
+            .ok_or(anyhow::anyhow!("no delegate: {}", id))?;
+
+        let (product, message) = &self.product[product_index];
+        let product = product.clone();
+        let state = message.delegate[delegate_index].1;
+
+        // 减仓委托，原委托，止盈委托，止损委托
+        let (hedge, delegate, profit, loss) = match state {
+            DelegateState::Single(v) => (None, v, None, None),
+            DelegateState::OpenProfit(v, a) => (None, v, Some(a), None),
+            DelegateState::OpenLoss(v, b) => (None, v, None, Some(b)),
+            DelegateState::OpenProfitLoss(v, a, b) => (None, v, Some(a), Some(b)),
+            DelegateState::Hedging(c, v) => (Some(c), v, None, None),
+            DelegateState::HedgingProfit(c, v, a) => (Some(c), v, Some(a), None),
+            DelegateState::HedgingLoss(c, v, b) => (Some(c), v, None, Some(b)),
+            DelegateState::HedgingProfitLoss(c, v, a, b) => (Some(c), v, Some(a), Some(b)),
+            DelegateState::ProfitLoss(a, b) => {
+                return self.amend_stop_inner(
+                    product_index,
+                    delegate_index,
+                    price,
+                    quantity,
+                    (stop_profit_condition, stop_profit, Some(a)),
+                    (stop_loss_condition, stop_loss, Some(b)),
+                );
+            }
+        };
+
+        // 成交后只剩下的止盈委托或者止损委托，市价和限价的止盈止损都可能只剩下一个
+        if let (DelegateState::Single(v), Some(position)) = (state, message.position.as_ref()) {
+            if v.side != position.side && self.is_stop(id, &v) {
+                // 多仓的止盈委托大于等于触发价，空仓的止盈委托小于等于触发价
+                let is_profit = matches!(
+                    v.price,
+                    Price::GreaterThanMarket(..) | Price::GreaterThanLimit(..)
+                ) == (position.side == Side::BuyLong);
+
+                let (profit, loss) = if is_profit {
+                    (Some(v), None)
+                } else {
+                    (None, Some(v))
+                };
+
+                return self.amend_stop_inner(
+                    product_index,
+                    delegate_index,
+                    price,
+                    quantity,
+                    (stop_profit_condition, stop_profit, profit),
+                    (stop_loss_condition, stop_loss, loss),
+                );
+            }
+        }
+
+        // 止盈止损的触发价格和委托价格
+        let condition = |v: Option<Delegate>| match v.map(|v| v.price) {
+            Some(Price::GreaterThanMarket(a) | Price::LessThanMarket(a)) => {
+                (Unit::Quantity(a), Unit::Ignore)
+            }
+            Some(Price::GreaterThanLimit(a, b) | Price::LessThanLimit(a, b)) => {
+                (Unit::Quantity(a), Unit::Quantity(b))
+            }
+            None => (Unit::Ignore, Unit::Ignore),
+        };
+
+        let (old_profit_condition, old_profit) = condition(profit);
+        let (old_loss_condition, old_loss) = condition(loss);
+
+        let or = |a: Unit, b: Unit| if a == Unit::Ignore { b } else { a };
+
+        let old_price = match delegate.price {
+            Price::GreaterThanMarket(v)
+            | Price::LessThanMarket(v)
+            | Price::GreaterThanLimit(v, _)
+            | Price::LessThanLimit(v, _) => v,
+        };
+
+        let open = delegate.side == Side::BuyLong || delegate.side == Side::SellShort;
+
+        // 平仓委托没有止盈止损，不能通过修改委托设置
+        if !open
+            && [
+                stop_profit_condition,
+                stop_loss_condition,
+                stop_profit,
+                stop_loss,
+            ]
+            .iter()
+            .any(|v| *v != Unit::Ignore)
+        {
+            anyhow::bail!(
+                "product {}: close delegate {} has no stop profit or stop loss",
+                product,
+                id
+            );
+        }
+
+        let quantity = match quantity {
+            Unit::Ignore => delegate.quantity,
+            Unit::Quantity(v) => v,
+            Unit::Proportion(_) => {
+                anyhow::bail!("product {}: amend quantity must be a quantity", product)
+            }
+        };
+
+        // 保持原委托的保证金比例
+        let margin = if !open || message.spot {
+            Unit::Ignore
+        } else {
+            let new_price = if price == 0.0 { message.k.close } else { price };
+            Unit::Quantity(delegate.margin / (old_price * delegate.quantity) * new_price * quantity)
+        };
+
+        let event = self.event.len();
+        let balance = self.balance;
+        let last_id = self.id;
+        let reserve = self.reserve.clone();
+
+        self.cancel_inner(id);
+        self.event.truncate(event);
+
+        // 反手委托的平仓部分成交后会释放仓位的保证金，检查余额时计入
+        let release = hedge.map_or(0.0, |v| v.margin);
+
+        self.balance += release;
+
+        let result = self.order_inner(
+            &product,
+            delegate.side,
+            price,
+            Unit::Quantity(quantity),
+            margin,
+            or(stop_profit_condition, old_profit_condition),
+            or(stop_loss_condition, old_loss_condition),
+            or(stop_profit, old_profit),
+            or(stop_loss, old_loss),
+        );
+
+        self.balance -= release;
+
+        let message = &mut self.product[product_index].1;
+
+        match result {
+            Ok(new_id) => {
+                // 恢复原来的委托 id 和位置，保留没有修改的字段
+                let (.., mut v) = message.delegate.pop().unwrap();
+
+                let keep = |new: &mut Delegate, old: Option<Delegate>| {
+                    if let Some(old) = old {
+                        new.append_margin = old.append_margin;
+                    }
+                };
+
+                match &mut v {
+                    DelegateState::Single(a) => keep(a, Some(delegate)),
+                    DelegateState::OpenProfit(a, b) => {
+                        keep(a, Some(delegate));
+                        keep(b, profit);
+                    }
+                    DelegateState::OpenLoss(a, c) => {
+                        keep(a, Some(delegate));
+                        keep(c, loss);
+                    }
+                    DelegateState::OpenProfitLoss(a, b, c) => {
+                        keep(a, Some(delegate));
+                        keep(b, profit);
+                        keep(c, loss);
+                    }
+                    _ => {}
+                }
+
+                // 反手委托的平仓部分和开仓部分使用相同的价格
+                if let Some(mut close) = hedge {
+                    v = match v {
+                        DelegateState::Single(a) => {
+                            close.price = a.price;
+                            DelegateState::Hedging(close, a)
+                        }
+                        DelegateState::OpenProfit(a, b) => {
+                            close.price = a.price;
+                            DelegateState::HedgingProfit(close, a, b)
+                        }
+                        DelegateState::OpenLoss(a, b) => {
+                            close.price = a.price;
+                            DelegateState::HedgingLoss(close, a, b)
+                        }
+                        DelegateState::OpenProfitLoss(a, b, c) => {
+                            close.price = a.price;
+                            DelegateState::HedgingProfitLoss(close, a, b, c)
+                        }
+                        v => v,
+                    };
+                }
+
+                message.delegate.insert(delegate_index, (id, v));
+                message
+                    .taker
+                    .iter_mut()
+                    .filter(|v| **v == new_id)
+                    .for_each(|v| *v = id);
+                self.reserve
+                    .iter_mut()
+                    .filter(|v| v.0 == new_id)
+                    .for_each(|v| v.0 = id);
+                self.id = last_id;
+                self.event.push(Event::Amend {
+                    id,
+                    product,
+                    time: message.k.time,
+                    delegate: v,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                message.delegate.insert(delegate_index, (id, state));
+                self.balance = balance;
+                self.reserve = reserve;
+                self.id = last_id;
+                Err(e)
+            }
+        }
+    }
+
+    /// 委托是否为开仓委托成交后留下的止盈止损委托。
+    /// 止盈止损委托是平仓方向，但是委托记录是开仓方向，以此和普通的平仓委托区分。
+    fn is_stop(&self, id: u64, delegate: &Delegate) -> bool {
+        let open = |side: Side| side == Side::BuyLong || side == Side::SellShort;
+        let id = self.split.iter().find(|v| v.0 == id).map_or(id, |v| v.1);
+
+        !open(delegate.side) && self.order_record(id).is_some_and(|v| open(v.side))
+    }
+
+    /// 修改仓位的止盈止损委托，委托 id 和排队位置以外的字段保持不变。
+    ///
+    /// * `price` 止盈止损委托没有单独的委托价格，必须为 0。
+    /// * `quantity` 委托数量，[`Unit::Ignore`] 表示不修改。
+    /// * `profit` 止盈触发价格，止盈委托价格，原止盈委托。
+    /// * `loss` 止损触发价格，止损委托价格，原止损委托。
+    fn amend_stop_inner(
+        &mut self,
+        product_index: usize,
+        delegate_index: usize,
+        price: f64,
+        quantity: Unit,
+        profit: (Unit, Unit, Option<Delegate>),
+        loss: (Unit, Unit, Option<Delegate>),
+    ) -> anyhow::Result<()> {
+        let (product, message) = &mut self.product[product_index];
+        let (id, state) = message.delegate[delegate_index];
+
+        if price != 0.0 {
+            anyhow::bail!(
+                "product {}: stop delegate {} price must be zero, use stop profit or stop loss",
+                product,
+                id
+            );
+        }
+
+        let position = message
+            .position
+            .as_ref()
+            .ok_or(anyhow::anyhow!("product {}: no position", product))?;
+
+        let quantity = match quantity {
+            Unit::Ignore => None,
+            Unit::Quantity(v) if v < message.min_size => anyhow::bail!(
+                "product {}: close quantity < min size: {} < {}",
+                product,
+                v,
+                message.min_size
+            ),
+            Unit::Quantity(v) if v > position.quantity => anyhow::bail!(
+                "product {}: close quantity > position quantity: {} > {}",
+                product,
+                v,
+                position.quantity
+            ),
+            Unit::Quantity(v) => Some(v),
+            Unit::Proportion(_) => {
+                anyhow::bail!("product {}: amend quantity must be a quantity", product)
+            }
+        };
+
+        let long = position.side == Side::BuyLong;
+        let close = message.k.close;
+
+        // 新的止盈止损委托，profit 表示是否为止盈
+        let amend = |(condition, limit, old): (Unit, Unit, Option<Delegate>),
+                     profit: bool|
+         -> anyhow::Result<Option<Delegate>> {
+            let name = if profit { "stop profit" } else { "stop loss" };
+
+            let Some(old) = old else {
+                if condition != Unit::Ignore || limit != Unit::Ignore {
+                    anyhow::bail!("product {}: delegate {} has no {}", product, id, name);
+                }
+
+                return Ok(None);
+            };
+
+            let (old_condition, old_limit) = match old.price {
+                Price::GreaterThanMarket(a) | Price::LessThanMarket(a) => (a, None),
+                Price::GreaterThanLimit(a, b) | Price::LessThanLimit(a, b) => (a, Some(b)),
+            };
+
+            let value = |v: Unit| match v {
+                Unit::Quantity(v) => Ok(Some(v)),
+                Unit::Ignore => Ok(None),
+                Unit::Proportion(_) => {
+                    anyhow::bail!("product {}: amend {} must be a quantity", product, name)
+                }
+            };
+
+            let condition = value(condition)?.unwrap_or(old_condition);
+            let limit = value(limit)?.or(old_limit);
+
+            if condition <= 0.0 || limit.is_some_and(|v| v <= 0.0) {
+                anyhow::bail!("product {}: {} invalid: {}", product, name, condition);
+            }
+
+            // 多仓的止盈和空仓的止损大于等于触发价
+            let greater = profit == long;
+
+            // 触发价格不能已经被当前价格触发
+            if greater && condition <= close || !greater && condition >= close {
+                anyhow::bail!(
+                    "product {}: {} condition has been triggered: {} {}",
+                    product,
+                    name,
+                    condition,
+                    close
+                );
+            }
+
+            let quantity = quantity.unwrap_or(old.quantity);
+
+            Ok(Some(Delegate {
+                price: match (greater, limit) {
+                    (true, Some(v)) => Price::GreaterThanLimit(condition, v),
+                    (true, None) => Price::GreaterThanMarket(condition),
+                    (false, Some(v)) => Price::LessThanLimit(condition, v),
+                    (false, None) => Price::LessThanMarket(condition),
+                },
+                quantity,
+                margin: old.margin * quantity / old.quantity,
+                ..old
+            }))
+        };
+
+        let state = match (state, amend(profit, true)?, amend(loss, false)?) {
+            (DelegateState::ProfitLoss(..), Some(a), Some(b)) => DelegateState::ProfitLoss(a, b),
+            (_, Some(v), None) | (_, None, Some(v)) => DelegateState::Single(v),
+            _ => anyhow::bail!("product {}: delegate {} can not be amended", product, id),
+        };
+
+        message.delegate[delegate_index].1 = state;
+        message.queue.retain(|v| v.0 != id);
+
+        self.event.push(Event::Amend {
+            id,
+            product: product.clone(),
+            time: message.k.time,
+            delegate: state,
+        });
+
+        Ok(())
+    }
+
+    fn cancel_inner(&mut self, id: u64) -> bool {
         if id == 0 {
-            self.product.iter_mut().for_each(|v| v.1.delegate.clear());
+            for (product, message) in self.product.iter_mut() {
+                for (id, ..) in message.delegate.drain(..) {
+                    self.event.push(Event::Cancel {
+                        id,
+                        product: product.clone(),
+                        time: message.k.time,
+                    });
+                }
+            }
+
             return true;
         }
 
         for i in self.product.iter_mut() {
             if let Some(v) = i.1.delegate.iter().position(|v| v.0 == id) {
+                // 现货在委托时没有扣除手续费，设置了手续费模型时退还预先扣除的估计手续费
+                let spot = i.1.spot;
+                let unit = reserved(&self.reserve, id);
+                let fee = |v: &Delegate| {
+                    if spot {
+                        0.0
+                    } else if self.fee.is_some() {
+                        unit * v.quantity
+                    } else {
+                        price_value(v.price) * v.quantity * self.config.open_fee
+                    }
+                };
+
                 match i.1.delegate[v].1 {
                     DelegateState::Single(v)
                         if v.side == Side::BuyLong || v.side == Side::SellShort =>
                     {
-                        self.balance += v.margin
-                            + match v.price {
-                                Price::GreaterThanMarket(v) => v,
-                                Price::LessThanMarket(v) => v,
-                                Price::GreaterThanLimit(v, _) => v,
-                                Price::LessThanLimit(v, _) => v,
-                            } * v.quantity
-                                * self.config.open_fee;
+                        self.balance += v.margin + fee(&v);
                     }
                     DelegateState::Hedging(.., v)
                     | DelegateState::HedgingProfit(_, v, ..)
@@ -681,27 +2355,178 @@ impl MatchEngine {
                     | DelegateState::OpenProfit(v, ..)
                     | DelegateState::OpenLoss(v, ..)
                     | DelegateState::OpenProfitLoss(v, ..) => {
-                        self.balance += v.margin
-                            + match v.price {
-                                Price::GreaterThanMarket(v) => v,
-                                Price::LessThanMarket(v) => v,
-                                Price::GreaterThanLimit(v, _) => v,
-                                Price::LessThanLimit(v, _) => v,
-                            } * v.quantity
-                                * self.config.open_fee;
+                        self.balance += v.margin + fee(&v);
+                    }
+                    _ => {}
+                }
+                self.reserve.retain(|v| v.0 != id);
+                i.1.delegate.remove(v);
+                self.event.push(Event::Cancel {
+                    id,
+                    product: i.0.clone(),
+                    time: i.1.k.time,
+                });
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// 刷新。
+    /// 按照 [`Config::path`] 把 k 线拆分成多段单向的价格变动，依次撮合。
+    pub fn update(&mut self) {
+        self.update_book();
+
+        if self.config.path == PricePath::Whole {
+            self.update_k();
+            self.product.iter_mut().for_each(|v| clear_fill(&mut v.1));
+            self.flush();
+            return;
+        }
+
+        let k = self.product.iter().map(|v| v.1.k).collect::<Vec<_>>();
+
+        let path = self
+            .product
+            .iter()
+            .map(|(.., v)| price_path(self.config.path, v))
+            .collect::<Vec<_>>();
+
+        let len = path.iter().map(|v| v.len()).max().unwrap_or(0);
+
+        for i in 0..len {
+            for ((.., message), (k, path)) in self.product.iter_mut().zip(k.iter().zip(&path)) {
+                message.k = path.get(i).copied().unwrap_or(K {
+                    time: k.time,
+                    open: k.close,
+                    high: k.close,
+                    low: k.close,
+                    close: k.close,
+                });
+            }
+
+            self.update_k();
+        }
+
+        for ((.., message), k) in self.product.iter_mut().zip(k) {
+            message.k = k;
+            clear_fill(message);
+        }
+
+        self.flush();
+    }
+
+    /// 使用盘口撮合时，确定本次刷新需要成交的委托和成交价格。
+    fn update_book(&mut self) {
+        for (
+            product,
+            Message {
+                k,
+                delegate,
+                book,
+                queue,
+                fill,
+                taker,
+                ..
+            },
+        ) in self.product.iter_mut()
+        {
+            let book = match book {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let last = k.close;
+
+            for (id, delegate_state) in delegate.iter_mut() {
+                // 触发条件委托，触发后的委托在之后按照盘口撮合
+                let triggered = match delegate_state {
+                    DelegateState::Single(v)
+                    | DelegateState::Hedging(v, ..)
+                    | DelegateState::HedgingProfit(v, ..)
+                    | DelegateState::HedgingLoss(v, ..)
+                    | DelegateState::HedgingProfitLoss(v, ..) => trigger(v, last).map(|price| {
+                        v.price = price;
+                        v.append_margin = 0.0;
+                        *v
+                    }),
+                    DelegateState::ProfitLoss(a, b) => {
+                        if let Some(price) = trigger(b, last) {
+                            b.price = price;
+                            b.append_margin = 0.0;
+                            let v = *b;
+                            *delegate_state = DelegateState::Single(v);
+                            Some(v)
+                        } else if let Some(price) = trigger(a, last) {
+                            a.price = price;
+                            a.append_margin = 0.0;
+                            let v = *a;
+                            *delegate_state = DelegateState::Single(v);
+                            Some(v)
+                        } else {
+                            None
+                        }
                     }
-                    _ => {}
+                    _ => None,
+                };
+
+                if let Some(v) = triggered {
+                    self.event.push(Event::Trigger {
+                        id: *id,
+                        product: product.clone(),
+                        time: k.time,
+                        delegate: v,
+                    });
+                }
+
+                for delegate in active_delegate(delegate_state) {
+                    let price = match passive(delegate, taker, *id) {
+                        Some((buy, price)) => {
+                            let queue = queue_mut(queue, book, *id, buy, price);
+
+                            // 排队轮到成交，或者对手盘价格穿过委托价格
+                            if *queue < 0.0
+                                || buy && book.best_ask().is_some_and(|v| v <= price)
+                                || !buy && book.best_bid().is_some_and(|v| v >= price)
+                            {
+                                price
+                            } else {
+                                continue;
+                            }
+                        }
+                        None => {
+                            let buy =
+                                delegate.side == Side::BuyLong || delegate.side == Side::SellLong;
+
+                            match delegate.price {
+                                Price::GreaterThanMarket(v) if last >= v => {}
+                                Price::LessThanMarket(v) if last <= v => {}
+                                _ => continue,
+                            }
+
+                            book.walk(buy, delegate.quantity).unwrap_or(last)
+                        }
+                    };
+
+                    delegate.price = match delegate.price {
+                        Price::GreaterThanMarket(_) => Price::GreaterThanMarket(price),
+                        _ => Price::LessThanMarket(price),
+                    };
+
+                    fill.push((*id, price));
+                    taker.retain(|v| v != id);
+
+                    break;
                 }
-                i.1.delegate.remove(v);
-                return true;
             }
-        }
 
-        false
+            queue.retain(|v| delegate.iter().any(|(id, ..)| *id == v.0));
+            taker.retain(|v| delegate.iter().any(|(id, ..)| id == v));
+        }
     }
 
-    /// 刷新。
-    pub fn update(&mut self) {
+    fn update_k(&mut self) {
         self.update_liquidation();
         self.update_close_delegate();
         self.update_open_delegate();
@@ -709,7 +2534,7 @@ impl MatchEngine {
     }
 
     fn update_liquidation(&mut self) {
-        for (.., Message { k, position, .. }) in self.product.iter_mut() {
+        for (product, Message { k, position, .. }) in self.product.iter_mut() {
             if position.is_none() {
                 continue;
             }
@@ -724,6 +2549,23 @@ impl MatchEngine {
                 continue;
             }
 
+            // 强平的手续费已经计入强平价格，损失仍然是全部保证金
+            // 设置了手续费模型时记录实际收取的手续费，没有设置时为 0
+            let fee = if self.fee.is_some() {
+                current_position.liquidation_price
+                    * current_position.quantity
+                    * liquidation_fee(
+                        &self.config,
+                        &self.fee,
+                        &self.volume,
+                        product,
+                        current_position.side,
+                        k.time,
+                    )
+            } else {
+                0.0
+            };
+
             let record = Record {
                 side: if current_position.side == Side::BuyLong {
                     Side::BuySell
@@ -733,212 +2575,288 @@ impl MatchEngine {
                 price: current_position.liquidation_price,
                 quantity: current_position.quantity,
                 margin: current_position.margin,
-                fee: 0.0,
-                profit: -current_position.margin,
+                fee,
+                profit: fee - current_position.margin,
                 profit_ratio: -1.0,
                 time: k.time,
+                maker: false,
             };
 
             current_position.log.push(record);
 
             self.history
                 .push(new_history_position(position.take().unwrap()));
+
+            self.event.push(Event::Liquidate {
+                time: k.time,
+                position: self.history.last().unwrap().clone(),
+            });
         }
     }
 
     fn update_close_delegate(&mut self) {
-        let mut handle =
-            |k: &K, delegate_state: &mut DelegateState, position: &mut Option<Position>| {
-                let mut flag = 0;
-
-                if k.time == 1692401400000 {
-                    println!("1")
-                }
-
-                macro_rules! remove_or_convert {
-                    () => {
-                        match delegate_state {
-                            DelegateState::Hedging(.., v) => {
-                                *delegate_state = DelegateState::Single(*v);
-                                false
-                            }
-                            DelegateState::HedgingProfit(.., a, b) => {
-                                *delegate_state = DelegateState::OpenProfit(*a, *b);
-                                false
-                            }
-                            DelegateState::HedgingLoss(.., a, b) => {
-                                *delegate_state = DelegateState::OpenLoss(*a, *b);
-                                false
-                            }
-                            DelegateState::HedgingProfitLoss(.., a, b, c) => {
-                                *delegate_state = DelegateState::OpenProfitLoss(*a, *b, *c);
-                                false
-                            }
-                            _ => true,
+        let mut handle = |product: &String,
+                          k: &K,
+                          id: u64,
+                          delegate_state: &mut DelegateState,
+                          position: &mut Option<Position>,
+                          spot: bool,
+                          taker: &[u64]| {
+            let mut flag = 0;
+
+            macro_rules! remove_or_convert {
+                () => {
+                    match delegate_state {
+                        DelegateState::Hedging(.., v) => {
+                            *delegate_state = DelegateState::Single(*v);
+                            false
                         }
-                    };
-                }
-
-                loop {
-                    let delegate = match delegate_state {
-                        DelegateState::Single(v)
-                            if v.side == Side::BuySell || v.side == Side::SellLong =>
-                        {
-                            v
+                        DelegateState::HedgingProfit(.., a, b) => {
+                            *delegate_state = DelegateState::OpenProfit(*a, *b);
+                            false
                         }
-                        DelegateState::Hedging(v, ..)
-                        | DelegateState::HedgingProfit(v, ..)
-                        | DelegateState::HedgingLoss(v, ..)
-                        | DelegateState::HedgingProfitLoss(v, ..) => v,
-                        DelegateState::ProfitLoss(a, b) => {
-                            if flag == 0 {
-                                flag = 1;
-                                b
-                            } else if flag == 1 {
-                                flag = 2;
-                                a
-                            } else {
-                                return false;
-                            }
+                        DelegateState::HedgingLoss(.., a, b) => {
+                            *delegate_state = DelegateState::OpenLoss(*a, *b);
+                            false
                         }
-                        _ => return false,
-                    };
-
-                    let current_position = if let Some(v) = position {
-                        // 如果委托方向不等于仓位方向，则撤销委托，这是由于对冲仓位导致的。
-                        if delegate.side == Side::BuySell && v.side == Side::SellShort
-                            || delegate.side == Side::SellLong && v.side == Side::BuyLong
-                        {
-                            return remove_or_convert!();
+                        DelegateState::HedgingProfitLoss(.., a, b, c) => {
+                            *delegate_state = DelegateState::OpenProfitLoss(*a, *b, *c);
+                            false
                         }
+                        _ => true,
+                    }
+                };
+            }
 
-                        // 如果平仓委托的平仓量大于持仓量，则撤销委托
-                        if delegate.quantity > v.quantity {
-                            return remove_or_convert!();
-                        }
+            macro_rules! expire {
+                () => {{
+                    let remove = remove_or_convert!();
 
-                        v
-                    } else {
-                        // 如果仓位被强平，则撤销委托
-                        return remove_or_convert!();
-                    };
+                    if remove {
+                        self.event.push(Event::Expire {
+                            id,
+                            product: product.clone(),
+                            time: k.time,
+                        });
+                    }
+
+                    return remove;
+                }};
+            }
 
-                    if !match delegate.price {
-                        Price::GreaterThanMarket(v) | Price::GreaterThanLimit(v, _) => k.high >= v,
-                        Price::LessThanMarket(v) | Price::LessThanLimit(v, _) => k.low <= v,
-                    } {
-                        if flag == 1 {
-                            continue;
+            loop {
+                let delegate = match delegate_state {
+                    DelegateState::Single(v)
+                        if v.side == Side::BuySell || v.side == Side::SellLong =>
+                    {
+                        v
+                    }
+                    DelegateState::Hedging(v, ..)
+                    | DelegateState::HedgingProfit(v, ..)
+                    | DelegateState::HedgingLoss(v, ..)
+                    | DelegateState::HedgingProfitLoss(v, ..) => v,
+                    DelegateState::ProfitLoss(a, b) => {
+                        if flag == 0 {
+                            flag = 1;
+                            b
+                        } else if flag == 1 {
+                            flag = 2;
+                            a
+                        } else {
+                            return false;
                         }
+                    }
+                    _ => return false,
+                };
+
+                let current_position = if let Some(v) = position {
+                    // 如果委托方向不等于仓位方向，则撤销委托，这是由于对冲仓位导致的。
+                    if delegate.side == Side::BuySell && v.side == Side::SellShort
+                        || delegate.side == Side::SellLong && v.side == Side::BuyLong
+                    {
+                        expire!();
+                    }
 
-                        return false;
+                    // 如果平仓委托的平仓量大于持仓量，则撤销委托
+                    if delegate.quantity > v.quantity {
+                        expire!();
                     }
 
-                    match delegate.price {
-                        Price::GreaterThanMarket(v) | Price::LessThanMarket(v) => {
-                            // 限价委托
-                            let profit = if current_position.side == Side::BuyLong {
-                                (v - current_position.open_price) * delegate.quantity
-                            } else {
-                                (current_position.open_price - v) * delegate.quantity
-                            };
+                    v
+                } else {
+                    // 如果仓位被强平，则撤销委托
+                    expire!();
+                };
 
-                            let record = Record {
-                                side: delegate.side,
-                                price: v,
-                                quantity: delegate.quantity,
-                                margin: delegate.margin + delegate.append_margin,
-                                fee: v * delegate.quantity * self.config.close_fee,
-                                profit,
-                                profit_ratio: profit / delegate.margin,
-                                time: k.time,
-                            };
+                if !match delegate.price {
+                    Price::GreaterThanMarket(v) | Price::GreaterThanLimit(v, _) => k.high >= v,
+                    Price::LessThanMarket(v) | Price::LessThanLimit(v, _) => k.low <= v,
+                } {
+                    if flag == 1 {
+                        continue;
+                    }
 
-                            self.balance += record.profit + record.margin - record.fee;
+                    return false;
+                }
 
-                            current_position.quantity -= delegate.quantity;
-                            current_position.margin -= delegate.margin;
-                            current_position.log.push(record);
+                match delegate.price {
+                    Price::GreaterThanMarket(v) | Price::LessThanMarket(v) => {
+                        // 限价委托
+                        // 现货的收益为卖出所得减去买入成本
+                        let profit = if spot {
+                            v * delegate.quantity - delegate.margin
+                        } else if current_position.side == Side::BuyLong {
+                            (v - current_position.open_price) * delegate.quantity
+                        } else {
+                            (current_position.open_price - v) * delegate.quantity
+                        };
 
-                            if current_position.quantity == 0.0 {
-                                self.history
-                                    .push(new_history_position(position.take().unwrap()));
-                            }
+                        let maker = passive(delegate, taker, id).is_some();
+
+                        let record = Record {
+                            side: delegate.side,
+                            price: v,
+                            quantity: delegate.quantity,
+                            margin: delegate.margin + delegate.append_margin,
+                            fee: v
+                                * delegate.quantity
+                                * fee_rate(
+                                    &self.config,
+                                    &self.fee,
+                                    &self.volume,
+                                    product,
+                                    delegate.side,
+                                    maker,
+                                    k.time,
+                                ),
+                            profit,
+                            profit_ratio: profit / delegate.margin,
+                            time: k.time,
+                            maker,
+                        };
+
+                        self.balance += record.profit + record.margin - record.fee;
 
-                            return remove_or_convert!();
+                        if spot {
+                            change_asset(
+                                &mut self.asset,
+                                &mut self.asset_history,
+                                k.time,
+                                base_asset(product),
+                                -delegate.quantity,
+                            );
                         }
-                        Price::GreaterThanLimit(a, b) | Price::LessThanLimit(a, b) => {
-                            // 限价触发，限价委托
-                            let temp = if delegate.side == Side::BuySell && a <= b {
-                                //                   C
-                                //          B        |
-                                // A        |        |
-                                // |        |        |
-                                // open  condition  price
-                                Delegate {
-                                    side: delegate.side,
-                                    price: Price::GreaterThanMarket(b),
-                                    quantity: delegate.quantity,
-                                    margin: delegate.margin,
-                                    append_margin: 0.0,
-                                }
-                            } else if delegate.side == Side::BuySell {
-                                //
-                                //          B
-                                // A        |        C
-                                // |        |        |
-                                // open  condition  price
-                                Delegate {
-                                    side: delegate.side,
-                                    price: Price::LessThanMarket(b),
-                                    quantity: delegate.quantity,
-                                    margin: delegate.margin,
-                                    append_margin: 0.0,
-                                }
-                            } else if delegate.side == Side::SellLong && a >= b {
-                                // A
-                                // |        B
-                                // |        |        C
-                                // |        |        |
-                                // open  condition  price
-                                Delegate {
-                                    side: delegate.side,
-                                    price: Price::LessThanMarket(b),
-                                    quantity: delegate.quantity,
-                                    margin: delegate.margin,
-                                    append_margin: 0.0,
-                                }
-                            } else {
-                                // A                 C
-                                // |        B        |
-                                // |        |        |
-                                // |        |        |
-                                // open  condition  price
-                                Delegate {
-                                    side: delegate.side,
-                                    price: Price::GreaterThanMarket(b),
-                                    quantity: delegate.quantity,
-                                    margin: delegate.margin,
-                                    append_margin: 0.0,
-                                }
-                            };
 
-                            if flag != 0 {
-                                *delegate_state = DelegateState::Single(temp);
-                            } else {
-                                *delegate = temp;
+                        current_position.quantity -= delegate.quantity;
+                        current_position.margin -= delegate.margin;
+                        current_position.log.push(record);
+
+                        self.event.push(Event::Fill {
+                            id,
+                            product: product.clone(),
+                            record,
+                        });
+
+                        if current_position.quantity == 0.0 {
+                            self.history
+                                .push(new_history_position(position.take().unwrap()));
+
+                            self.event.push(Event::Close {
+                                time: k.time,
+                                position: self.history.last().unwrap().clone(),
+                            });
+                        } else {
+                            self.event.push(Event::Reduce {
+                                time: k.time,
+                                position: current_position.clone(),
+                            });
+                        }
+
+                        return remove_or_convert!();
+                    }
+                    Price::GreaterThanLimit(a, b) | Price::LessThanLimit(a, b) => {
+                        // 限价触发，限价委托
+                        let temp = if delegate.side == Side::BuySell && a <= b {
+                            //                   C
+                            //          B        |
+                            // A        |        |
+                            // |        |        |
+                            // open  condition  price
+                            Delegate {
+                                side: delegate.side,
+                                price: Price::GreaterThanMarket(b),
+                                quantity: delegate.quantity,
+                                margin: delegate.margin,
+                                append_margin: 0.0,
+                            }
+                        } else if delegate.side == Side::BuySell {
+                            //
+                            //          B
+                            // A        |        C
+                            // |        |        |
+                            // open  condition  price
+                            Delegate {
+                                side: delegate.side,
+                                price: Price::LessThanMarket(b),
+                                quantity: delegate.quantity,
+                                margin: delegate.margin,
+                                append_margin: 0.0,
+                            }
+                        } else if delegate.side == Side::SellLong && a >= b {
+                            // A
+                            // |        B
+                            // |        |        C
+                            // |        |        |
+                            // open  condition  price
+                            Delegate {
+                                side: delegate.side,
+                                price: Price::LessThanMarket(b),
+                                quantity: delegate.quantity,
+                                margin: delegate.margin,
+                                append_margin: 0.0,
+                            }
+                        } else {
+                            // A                 C
+                            // |        B        |
+                            // |        |        |
+                            // |        |        |
+                            // open  condition  price
+                            Delegate {
+                                side: delegate.side,
+                                price: Price::GreaterThanMarket(b),
+                                quantity: delegate.quantity,
+                                margin: delegate.margin,
+                                append_margin: 0.0,
                             }
+                        };
+
+                        if flag != 0 {
+                            *delegate_state = DelegateState::Single(temp);
+                        } else {
+                            *delegate = temp;
                         }
+
+                        self.event.push(Event::Trigger {
+                            id,
+                            product: product.clone(),
+                            time: k.time,
+                            delegate: temp,
+                        });
                     }
                 }
-            };
+            }
+        };
 
         for (
-            ..,
+            product,
             Message {
                 k,
                 delegate,
                 position,
+                spot,
+                book,
+                fill,
+                taker,
                 ..
             },
         ) in self.product.iter_mut()
@@ -946,7 +2864,17 @@ impl MatchEngine {
             let mut i = 0;
 
             while i < delegate.len() {
-                if handle(k, &mut delegate[i].1, position) {
+                let k = book_k(k, book, fill, delegate[i].0);
+
+                if handle(
+                    product,
+                    &k,
+                    delegate[i].0,
+                    &mut delegate[i].1,
+                    position,
+                    *spot,
+                    taker,
+                ) {
                     delegate.remove(i);
                 } else {
                     i += 1;
@@ -956,17 +2884,15 @@ impl MatchEngine {
     }
 
     fn update_open_delegate(&mut self) {
-        enum State {
-            Next,
-            Close(DelegateState),
-            ReloadRemove,
-            Remove,
-        }
-
-        let handle = |product: &String,
-                      k: &K,
-                      delegate_state: &mut DelegateState,
-                      position: &mut Option<Position>| {
+        let mut handle = |product: &String,
+                          k: &K,
+                          id: u64,
+                          delegate_state: &mut DelegateState,
+                          position: &mut Option<Position>,
+                          tier: &Vec<Tier>,
+                          spot: bool,
+                          lever: u32,
+                          taker: &[u64]| {
             let delegate = match delegate_state {
                 DelegateState::Single(v)
                     if v.side == Side::BuyLong || v.side == Side::SellShort =>
@@ -993,6 +2919,34 @@ impl MatchEngine {
                 Price::LessThanLimit(v, _) => v,
             };
 
+            let maker = passive(delegate, taker, id).is_some();
+
+            let rate = fee_rate(
+                &self.config,
+                &self.fee,
+                &self.volume,
+                product,
+                delegate.side,
+                maker,
+                k.time,
+            );
+
+            if spot {
+                return spot_open(
+                    product,
+                    k,
+                    id,
+                    price,
+                    delegate_state,
+                    position,
+                    rate,
+                    maker,
+                    &mut self.asset,
+                    &mut self.asset_history,
+                    &mut self.event,
+                );
+            }
+
             // 计算开仓均价
             // 新方向，新价格，新持仓量，新保证金，追加保证金
             let (new_side, new_price, new_quantity, new_margin, append_margin) = match position {
@@ -1005,10 +2959,8 @@ impl MatchEngine {
                             + (price * delegate.quantity))
                             / (v.quantity + delegate.quantity);
 
-                        let append_margin = (v.margin
-                            - v.open_price * v.quantity / self.config.lever as f64)
-                            + (delegate.margin
-                                - open_price * delegate.quantity / self.config.lever as f64);
+                        let append_margin = (v.margin - v.open_price * v.quantity / lever as f64)
+                            + (delegate.margin - open_price * delegate.quantity / lever as f64);
 
                         (
                             delegate.side,
@@ -1020,7 +2972,9 @@ impl MatchEngine {
                     } else {
                         // 虽然在委托的时候会处理减仓，但是要存在仓位的时候才会减仓
                         // 这里处理多个委托同时成交，且方向不同的情况
+                        // 转换成平仓的部分退还预先扣除的估计手续费
                         return if v.quantity < delegate.quantity {
+                            self.balance += reserved(&self.reserve, id) * v.quantity;
                             let new_margin = v.quantity / delegate.quantity * delegate.margin;
                             let sub_margin = delegate.margin - new_margin;
                             delegate.quantity = delegate.quantity - v.quantity;
@@ -1037,6 +2991,7 @@ impl MatchEngine {
                                 append_margin: sub_margin,
                             }))
                         } else {
+                            self.balance += reserved(&self.reserve, id) * delegate.quantity;
                             delegate.side = if v.side == Side::BuyLong {
                                 Side::BuySell
                             } else {
@@ -1054,24 +3009,36 @@ impl MatchEngine {
                     price,
                     delegate.quantity,
                     delegate.margin,
-                    delegate.margin - price * delegate.quantity / self.config.lever as f64,
+                    delegate.margin - price * delegate.quantity / lever as f64,
                 ),
             };
 
             // 计算吃单手续费是为了防止穿仓，即余额不够支付手续费的情况
-            // 做多强平价格 = 入场价格 × (1 - 初始保证金率 + 维持保证金率) - (追加保证金 / 仓位数量) + 吃单手续费
-            // 做空强平价格 = 入场价格 × (1 + 初始保证金率 - 维持保证金率) + (追加保证金 / 仓位数量) - 吃单手续费
+            // 做多强平价格 = 入场价格 × (1 - 初始保证金率 + 维持保证金率) - (追加保证金 / 仓位数量) - (维持保证金速算额 / 仓位数量) + 吃单手续费
+            // 做空强平价格 = 入场价格 × (1 + 初始保证金率 - 维持保证金率) + (追加保证金 / 仓位数量) + (维持保证金速算额 / 仓位数量) - 吃单手续费
             // 初始保证金率 = 1 / 杠杆
             // 追加保证金 = 账户余额 - 初始化保证金
             // 初始保证金 = 入场价格 / 杠杆
-            let imr = 1.0 / self.config.lever as f64;
-            let mmr = self.config.maintenance;
+            let imr = 1.0 / lever as f64;
+            let (mmr, amount) = maintenance(&self.config, tier, new_price * new_quantity);
+            let close_fee = liquidation_fee(
+                &self.config,
+                &self.fee,
+                &self.volume,
+                product,
+                new_side,
+                k.time,
+            );
             let mut liquidation_price = if new_side == Side::BuyLong {
-                new_price * (1.0 - imr + mmr) - (append_margin / new_quantity)
-                    + price * delegate.quantity * self.config.close_fee
+                new_price * (1.0 - imr + mmr)
+                    - (append_margin / new_quantity)
+                    - (amount / new_quantity)
+                    + price * delegate.quantity * close_fee
             } else {
-                new_price * (1.0 + imr - mmr) + (append_margin / new_quantity)
-                    - price * delegate.quantity * self.config.close_fee
+                new_price * (1.0 + imr - mmr)
+                    + (append_margin / new_quantity)
+                    + (amount / new_quantity)
+                    - price * delegate.quantity * close_fee
             };
 
             if liquidation_price < 0.0 {
@@ -1091,12 +3058,18 @@ impl MatchEngine {
                 price,
                 quantity: delegate.quantity,
                 margin: delegate.margin,
-                fee: price * delegate.quantity * self.config.open_fee,
+                fee: price * delegate.quantity * rate,
                 profit: 0.0,
                 profit_ratio: 0.0,
                 time: k.time,
+                maker,
             };
 
+            // 设置了手续费模型时，结算预先扣除的估计手续费和实际手续费的差额
+            if self.fee.is_some() {
+                self.balance += reserved(&self.reserve, id) * delegate.quantity - record.fee;
+            }
+
             match position {
                 Some(v) => {
                     // 如果已经存在仓位，则直接修改仓位
@@ -1106,12 +3079,23 @@ impl MatchEngine {
                     v.margin = new_margin;
                     v.liquidation_price = liquidation_price;
                     v.log.push(record);
+
+                    self.event.push(Event::Fill {
+                        id,
+                        product: product.clone(),
+                        record,
+                    });
+
+                    self.event.push(Event::Increase {
+                        time: k.time,
+                        position: v.clone(),
+                    });
                 }
                 None => {
                     // 新建仓位
                     let mut current_position = Position {
                         product: product.clone(),
-                        lever: self.config.lever,
+                        lever,
                         side: new_side,
                         open_price: new_price,
                         quantity: new_quantity,
@@ -1124,10 +3108,22 @@ impl MatchEngine {
                         open_time: k.time,
                         close_time: 0,
                         log: Vec::new(),
+                        excursion: Excursion::new(record.price),
                     };
 
                     current_position.log.push(record);
 
+                    self.event.push(Event::Fill {
+                        id,
+                        product: product.clone(),
+                        record,
+                    });
+
+                    self.event.push(Event::Open {
+                        time: k.time,
+                        position: current_position.clone(),
+                    });
+
                     position.replace(current_position);
                 }
             };
@@ -1155,6 +3151,12 @@ impl MatchEngine {
                 k,
                 delegate,
                 position,
+                tier,
+                spot,
+                book,
+                fill,
+                lever,
+                taker,
                 ..
             },
         ) in self.product.iter_mut()
@@ -1162,12 +3164,44 @@ impl MatchEngine {
             let mut i = 0;
 
             while i < delegate.len() {
-                match handle(product, k, &mut delegate[i].1, position) {
+                let id = delegate[i].0;
+                let k = book_k(k, book, fill, id);
+
+                match handle(
+                    product,
+                    &k,
+                    id,
+                    &mut delegate[i].1,
+                    position,
+                    tier,
+                    *spot,
+                    *lever,
+                    taker,
+                ) {
                     State::Next => {
                         i += 1;
                     }
                     State::Close(v) => {
-                        delegate.insert(0, (0, v));
+                        // 拆分出来的平仓委托使用新的 id，成交记录到原委托
+                        self.id += 1;
+                        self.split.push((self.id, id));
+                        self.event.push(Event::Split {
+                            id: self.id,
+                            parent: id,
+                            product: product.clone(),
+                            time: k.time,
+                        });
+
+                        // 拆分出来的平仓委托和原委托以相同的价格成交
+                        if let Some(v) = fill.iter().find(|v| v.0 == id).copied() {
+                            fill.push((self.id, v.1));
+                        }
+
+                        if taker.contains(&id) {
+                            taker.push(self.id);
+                        }
+
+                        delegate.insert(0, (self.id, v));
                         self.update_close_delegate();
                         self.update_open_delegate();
                         return;
@@ -1186,20 +3220,491 @@ impl MatchEngine {
     }
 
     fn update_profit_loss(&mut self) {
-        for (.., Message { k, position, .. }) in self.product.iter_mut() {
+        for (
+            ..,
+            Message {
+                k,
+                bar,
+                position,
+                spot,
+                ..
+            },
+        ) in self.product.iter_mut()
+        {
             if let Some(v) = position {
-                let profit = if v.side == Side::BuyLong {
+                let profit = if *spot {
+                    k.close * v.quantity - v.margin
+                } else if v.side == Side::BuyLong {
                     (k.close - v.open_price) * v.quantity
                 } else {
                     (v.open_price - k.close) * v.quantity
                 };
                 v.profit = profit;
-                v.profit_ratio = profit / v.margin
+                v.profit_ratio = profit / v.margin;
+
+                // 开仓的 k 线不知道开仓之前的价格，只使用收盘价
+                let (high, low) = if v.open_time == k.time {
+                    (k.close, k.close)
+                } else {
+                    (k.high, k.low)
+                };
+                let mut excursion = v.excursion;
+                excursion.update(v, v.quantity, high, k.time);
+                excursion.update(v, v.quantity, low, k.time);
+                excursion.count(*bar);
+                v.excursion = excursion;
             }
         }
     }
 }
 
+enum State {
+    Next,
+    Close(DelegateState),
+    ReloadRemove,
+    Remove,
+}
+
+/// 现货买入成交。
+/// 手续费从收到的基础货币中扣除，止盈止损委托的数量同步扣除手续费。
+#[allow(clippy::too_many_arguments)]
+fn spot_open(
+    product: &str,
+    k: &K,
+    id: u64,
+    price: f64,
+    delegate_state: &mut DelegateState,
+    position: &mut Option<Position>,
+    open_fee: f64,
+    maker: bool,
+    asset: &mut Vec<(String, f64)>,
+    asset_history: &mut Vec<(u64, String, f64)>,
+    event: &mut Vec<Event>,
+) -> State {
+    let delegate = match delegate_state {
+        DelegateState::Single(v)
+        | DelegateState::OpenProfit(v, ..)
+        | DelegateState::OpenLoss(v, ..)
+        | DelegateState::OpenProfitLoss(v, ..) => *v,
+        _ => return State::Next,
+    };
+
+    // 实际收到的数量
+    let quantity = delegate.quantity * (1.0 - open_fee);
+
+    let record = Record {
+        side: delegate.side,
+        price,
+        quantity,
+        margin: delegate.margin,
+        fee: price * delegate.quantity * open_fee,
+        profit: 0.0,
+        profit_ratio: 0.0,
+        time: k.time,
+        maker,
+    };
+
+    event.push(Event::Fill {
+        id,
+        product: product.to_string(),
+        record,
+    });
+
+    match position {
+        Some(v) => {
+            v.open_price = (v.open_price * v.quantity + price * quantity) / (v.quantity + quantity);
+            v.quantity += quantity;
+            v.margin += delegate.margin;
+            v.log.push(record);
+
+            event.push(Event::Increase {
+                time: k.time,
+                position: v.clone(),
+            });
+        }
+        None => {
+            let current_position = position.insert(Position {
+                product: product.to_string(),
+                lever: 1,
+                side: Side::BuyLong,
+                open_price: price,
+                quantity,
+                margin: delegate.margin,
+                liquidation_price: 0.0,
+                close_price: 0.0,
+                profit: 0.0,
+                profit_ratio: 0.0,
+                fee: 0.0,
+                open_time: k.time,
+                close_time: 0,
+                log: vec![record],
+                excursion: Excursion::new(price),
+            });
+
+            event.push(Event::Open {
+                time: k.time,
+                position: current_position.clone(),
+            });
+        }
+    }
+
+    change_asset(asset, asset_history, k.time, base_asset(product), quantity);
+
+    // 止盈止损的数量不能超过实际收到的数量
+    let clamp = |v: &mut Delegate| v.quantity = v.quantity.min(quantity);
+
+    match delegate_state {
+        DelegateState::OpenProfit(.., v) | DelegateState::OpenLoss(.., v) => {
+            clamp(v);
+            *delegate_state = DelegateState::Single(*v);
+            State::Next
+        }
+        DelegateState::OpenProfitLoss(.., a, b) => {
+            clamp(a);
+            clamp(b);
+            *delegate_state = DelegateState::ProfitLoss(*a, *b);
+            State::Next
+        }
+        _ => State::Remove,
+    }
+}
+
+/// 获取开仓委托预先扣除的每单位数量的估计手续费，没有预先扣除时为 0。
+fn reserved(reserve: &[(u64, f64)], id: u64) -> f64 {
+    reserve.iter().find(|v| v.0 == id).map_or(0.0, |v| v.1)
+}
+
+/// 获取委托价格，限价委托为限价。
+fn price_value(price: Price) -> f64 {
+    match price {
+        Price::GreaterThanMarket(v)
+        | Price::LessThanMarket(v)
+        | Price::GreaterThanLimit(_, v)
+        | Price::LessThanLimit(_, v) => v,
+    }
+}
+
+/// 获取委托当前可以成交的部分，平仓委托和开仓委托。
+fn active_delegate(delegate_state: &mut DelegateState) -> Vec<&mut Delegate> {
+    match delegate_state {
+        DelegateState::Single(v)
+        | DelegateState::Hedging(v, ..)
+        | DelegateState::HedgingProfit(v, ..)
+        | DelegateState::HedgingLoss(v, ..)
+        | DelegateState::HedgingProfitLoss(v, ..)
+        | DelegateState::OpenProfit(v, ..)
+        | DelegateState::OpenLoss(v, ..)
+        | DelegateState::OpenProfitLoss(v, ..) => vec![v],
+        DelegateState::ProfitLoss(a, b) => vec![a, b],
+    }
+}
+
+/// 手续费模型统计成交额的时间范围，30 天。
+const VOLUME_PERIOD: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// 获取手续费率，没有设置手续费模型时，开仓使用 [`Config::open_fee`]，平仓使用 [`Config::close_fee`]。
+fn fee_rate(
+    config: &Config,
+    fee: &Option<Box<dyn Fee>>,
+    volume: &[(u64, f64)],
+    product: &str,
+    side: Side,
+    maker: bool,
+    time: u64,
+) -> f64 {
+    match fee {
+        Some(v) => v.rate(product, side, maker, trailing_volume(volume, time)),
+        None if side == Side::BuyLong || side == Side::SellShort => config.open_fee,
+        None => config.close_fee,
+    }
+}
+
+/// 强平按照吃单平仓的手续费率，计算强平价格和强平的手续费使用相同的费率。
+///
+/// * `side` 仓位方向。
+fn liquidation_fee(
+    config: &Config,
+    fee: &Option<Box<dyn Fee>>,
+    volume: &[(u64, f64)],
+    product: &str,
+    side: Side,
+    time: u64,
+) -> f64 {
+    let side = if side == Side::BuyLong {
+        Side::BuySell
+    } else {
+        Side::SellLong
+    };
+
+    fee_rate(config, fee, volume, product, side, false, time)
+}
+
+/// 统计过去 30 天的成交额。
+fn trailing_volume(volume: &[(u64, f64)], time: u64) -> f64 {
+    volume
+        .iter()
+        .filter(|v| v.0 + VOLUME_PERIOD > time)
+        .map(|v| v.1)
+        .sum()
+}
+
+/// 清除本次刷新需要成交的委托，和已经不存在的吃单。
+fn clear_fill(message: &mut Message) {
+    message.fill.clear();
+    message
+        .taker
+        .retain(|v| message.delegate.iter().any(|(id, ..)| id == v));
+}
+
+/// 根据委托价格和收盘价确定委托的触发方向。
+/// 限价委托的价格等于收盘价时作为挂单，即买入为 [`Price::LessThanMarket`]，卖出为 [`Price::GreaterThanMarket`]。
+///
+/// * `side` 委托方向。
+/// * `price` 委托价格，市价委托为收盘价。
+/// * `close` 收盘价。
+/// * `market` 是否为市价委托。
+fn delegate_price(side: Side, price: f64, close: f64, market: bool) -> Price {
+    let buy = side == Side::BuyLong || side == Side::SellLong;
+
+    if price > close || (price == close && (market || !buy)) {
+        Price::GreaterThanMarket(price)
+    } else {
+        Price::LessThanMarket(price)
+    }
+}
+
+/// 是否为挂单。
+/// 挂单是指委托价格优于触发价的一侧，市价委托除外。
+///
+/// * `return` 是否为买入，委托价格，不是挂单时返回 None。
+fn passive(delegate: &Delegate, taker: &[u64], id: u64) -> Option<(bool, f64)> {
+    if taker.contains(&id) {
+        return None;
+    }
+
+    let buy = delegate.side == Side::BuyLong || delegate.side == Side::SellLong;
+
+    match delegate.price {
+        Price::LessThanMarket(v) if buy => Some((true, v)),
+        Price::GreaterThanMarket(v) if !buy => Some((false, v)),
+        _ => None,
+    }
+}
+
+/// 获取挂单的排队位置，不存在时按照当前价格档位的数量插入。
+fn queue_mut<'a>(
+    queue: &'a mut Vec<(u64, f64, f64)>,
+    book: &OrderBook,
+    id: u64,
+    buy: bool,
+    price: f64,
+) -> &'a mut f64 {
+    let index = match queue.iter().position(|v| v.0 == id && v.1 == price) {
+        Some(v) => v,
+        None => {
+            queue.push((id, price, book.size(buy, price)));
+            queue.len() - 1
+        }
+    };
+
+    &mut queue[index].2
+}
+
+/// 条件委托是否被触发。
+/// 和 [`MatchEngine::update`] 处理条件委托的方式相同。
+///
+/// * `delegate` 委托。
+/// * `last` 最新成交价格。
+/// * `return` 触发后的委托价格。
+fn trigger(delegate: &Delegate, last: f64) -> Option<Price> {
+    let (a, b) = match delegate.price {
+        Price::GreaterThanLimit(a, b) if last >= a => (a, b),
+        Price::LessThanLimit(a, b) if last <= a => (a, b),
+        _ => return None,
+    };
+
+    Some(
+        if delegate.side == Side::BuySell && a <= b || delegate.side == Side::SellLong && a < b {
+            Price::GreaterThanMarket(b)
+        } else {
+            Price::LessThanMarket(b)
+        },
+    )
+}
+
+/// 使用盘口撮合时，每个委托使用单独的 k 线，需要成交的委托使用成交价格，其他委托使用不会触发任何价格的 k 线。
+fn book_k(k: &K, book: &Option<OrderBook>, fill: &[(u64, f64)], id: u64) -> K {
+    if book.is_none() {
+        return *k;
+    }
+
+    match fill.iter().find(|v| v.0 == id) {
+        Some(&(_, price)) => K {
+            time: k.time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        },
+        None => K {
+            time: k.time,
+            open: k.close,
+            high: f64::NEG_INFINITY,
+            low: f64::INFINITY,
+            close: k.close,
+        },
+    }
+}
+
+/// 把 k 线拆分成多段单向的价格变动。
+///
+/// * `path` 价格路径。
+/// * `message` 产品信息。
+/// * `return` 每一段价格变动的 k 线。
+fn price_path(path: PricePath, message: &Message) -> Vec<K> {
+    let k = message.k;
+
+    let point = match path {
+        PricePath::Whole => return vec![k],
+        PricePath::OpenHighLowClose => [k.open, k.high, k.low, k.close],
+        PricePath::OpenLowHighClose => [k.open, k.low, k.high, k.close],
+        PricePath::Pessimistic => match message.position {
+            Some(ref v) if v.side == Side::BuyLong => [k.open, k.low, k.high, k.close],
+            Some(_) => [k.open, k.high, k.low, k.close],
+            None => match pending_side(message) {
+                Some(Side::BuyLong) => [k.open, k.high, k.low, k.close],
+                Some(_) => [k.open, k.low, k.high, k.close],
+                None => nearest(&k),
+            },
+        },
+        PricePath::Nearest => nearest(&k),
+        PricePath::Lower(_) if message.lower.is_empty() => nearest(&k),
+        PricePath::Lower(_) => {
+            return message
+                .lower
+                .iter()
+                .flat_map(|v| segment(v.time, &nearest(v)))
+                .collect()
+        }
+    };
+
+    segment(k.time, &point)
+}
+
+/// 获取挂单的开仓方向，所有开仓挂单方向相同时返回该方向，没有挂单或者方向不一致时返回 None。
+fn pending_side(message: &Message) -> Option<Side> {
+    let mut iter = message.delegate.iter().filter_map(|(_, v)| match v {
+        DelegateState::Single(v)
+        | DelegateState::OpenProfit(v, ..)
+        | DelegateState::OpenLoss(v, ..)
+        | DelegateState::OpenProfitLoss(v, ..)
+            if v.side == Side::BuyLong || v.side == Side::SellShort =>
+        {
+            Some(v.side)
+        }
+        _ => None,
+    });
+
+    let side = iter.next()?;
+
+    iter.all(|v| v == side).then_some(side)
+}
+
+/// 先到达离开盘价更近的极值。
+fn nearest(k: &K) -> [f64; 4] {
+    if k.high - k.open <= k.open - k.low {
+        [k.open, k.high, k.low, k.close]
+    } else {
+        [k.open, k.low, k.high, k.close]
+    }
+}
+
+/// 把价格点连接成单向的价格变动。
+fn segment(time: u64, point: &[f64]) -> Vec<K> {
+    point
+        .windows(2)
+        .map(|v| K {
+            time,
+            open: v[0],
+            high: v[0].max(v[1]),
+            low: v[0].min(v[1]),
+            close: v[1],
+        })
+        .collect()
+}
+
+/// 获取现货产品的基础货币，例如 BTC-USDT 的基础货币为 BTC。
+fn base_asset(product: &str) -> &str {
+    product.split('-').next().unwrap_or(product)
+}
+
+/// 修改现货资产数量，并记录变化。
+fn change_asset(
+    asset: &mut Vec<(String, f64)>,
+    asset_history: &mut Vec<(u64, String, f64)>,
+    time: u64,
+    name: &str,
+    quantity: f64,
+) {
+    let value = match asset.iter_mut().find(|v| v.0 == name) {
+        Some(v) => {
+            v.1 += quantity;
+            v.1
+        }
+        None => {
+            asset.push((name.to_string(), quantity));
+            quantity
+        }
+    };
+
+    asset_history.push((time, name.to_string(), value));
+}
+
+/// 获取维持保证金率和维持保证金速算额。
+///
+/// * `config` 交易配置。
+/// * `tier` 风险档位，为空时使用 [`Config::maintenance`]。
+/// * `notional` 仓位价值。
+/// * `return` 维持保证金率，维持保证金速算额，超过最后一个档位时使用最后一个档位。
+fn maintenance(config: &Config, tier: &[Tier], notional: f64) -> (f64, f64) {
+    match tier
+        .iter()
+        .find(|v| notional <= v.max_notional)
+        .or(tier.last())
+    {
+        Some(v) => (v.maintenance, v.maintenance_amount),
+        None => (config.maintenance, 0.0),
+    }
+}
+
+/// 计算逐仓仓位的强平价格，手续费和保证金的计算参考 [`MatchEngine::update`] 中的开仓。
+///
+/// * `rate` 强平的手续费率，参考 [`liquidation_fee`]。
+#[allow(clippy::too_many_arguments)]
+fn liquidation_price(
+    config: &Config,
+    tier: &[Tier],
+    lever: u32,
+    side: Side,
+    open_price: f64,
+    quantity: f64,
+    margin: f64,
+    rate: f64,
+) -> f64 {
+    let imr = 1.0 / lever as f64;
+    let (mmr, amount) = maintenance(config, tier, open_price * quantity);
+    let append_margin = margin - open_price * quantity / lever as f64;
+    let fee = open_price * quantity * rate;
+
+    let price = if side == Side::BuyLong {
+        open_price * (1.0 - imr + mmr) - (append_margin / quantity) - (amount / quantity) + fee
+    } else {
+        open_price * (1.0 + imr - mmr) + (append_margin / quantity) + (amount / quantity) - fee
+    };
+
+    price.max(0.0)
+}
+
 /// 根据 log 统计仓位。
 ///
 /// * `最大持仓量`。
@@ -1218,7 +3723,14 @@ fn new_history_position(mut position: Position) -> Position {
     let mut max_margin = 0.0;
     let mut sum_margin = 0.0;
 
+    // 平仓的 k 线只使用成交价格
+    let mut excursion = position.excursion;
+
     position.log.iter().for_each(|v| {
+        if v.side == Side::BuySell || v.side == Side::SellLong {
+            excursion.update(&position, sum_quantity, v.price, v.time);
+        }
+
         sum_quantity += if v.side == Side::BuyLong || v.side == Side::SellShort {
             v.quantity
         } else {
@@ -1240,6 +3752,7 @@ fn new_history_position(mut position: Position) -> Position {
         }
     });
 
+    position.excursion = excursion;
     position.quantity = max_quantity;
     position.margin = max_margin;
     position.close_price = position.log.last().unwrap().price;
@@ -0,0 +1,323 @@
+//! k 线形态识别，数据系列都是新的数据在前面，只识别以当前 k 线结束的形态。
+
+use crate::*;
+
+/// k 线形态。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Pattern {
+    /// 吞没，当前 k 线的实体完全包住前一根相反方向 k 线的实体。
+    Engulfing,
+
+    /// 锤子线，下跌之后出现的长下影线小实体。
+    Hammer,
+
+    /// 射击之星，上涨之后出现的长上影线小实体。
+    ShootingStar,
+
+    /// 十字星，开盘价和收盘价几乎相等。
+    Doji,
+
+    /// 启明星，长阴线，小实体，长阳线。
+    MorningStar,
+
+    /// 黄昏星，长阳线，小实体，长阴线。
+    EveningStar,
+
+    /// 红三兵，连续三根上涨的阳线。
+    ThreeWhiteSoldiers,
+
+    /// 三只乌鸦，连续三根下跌的阴线。
+    ThreeBlackCrows,
+
+    /// 内包线，最高价和最低价都在前一根 k 线的范围之内。
+    InsideBar,
+
+    /// 外包线，最高价和最低价都超出前一根 k 线的范围。
+    OutsideBar,
+}
+
+/// 形态的方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Direction {
+    /// 看涨。
+    Bullish,
+
+    /// 看跌。
+    Bearish,
+
+    /// 中性。
+    Neutral,
+}
+
+/// 识别到的形态。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PatternSignal {
+    /// 形态。
+    pub pattern: Pattern,
+
+    /// 方向。
+    pub direction: Direction,
+
+    /// 强度，0 到 1，越大表示形态越标准。
+    pub strength: f64,
+}
+
+impl PatternSignal {
+    fn new(pattern: Pattern, direction: Direction, strength: f64) -> Self {
+        Self {
+            pattern,
+            direction,
+            strength: strength.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// 第 `index` 根 k 线的实体，上影线，下影线，振幅。
+fn shape(open: &Source, high: &Source, low: &Source, close: &Source, index: usize) -> [f64; 4] {
+    let (open, high, low, close) = (open[index], high[index], low[index], close[index]);
+    [
+        (close - open).abs(),
+        high - open.max(close),
+        open.min(close) - low,
+        high - low,
+    ]
+}
+
+/// 吞没。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn engulfing(
+    open: &Source,
+    high: &Source,
+    low: &Source,
+    close: &Source,
+) -> Option<PatternSignal> {
+    let [body, ..] = shape(open, high, low, close, 0);
+    let [prev_body, ..] = shape(open, high, low, close, 1);
+    let strength = 1.0 - prev_body / body;
+
+    if close[1] < open[1] && close > open && open <= close[1] && close >= open[1] {
+        return (body > prev_body)
+            .then(|| PatternSignal::new(Pattern::Engulfing, Direction::Bullish, strength));
+    }
+
+    if close[1] > open[1] && close < open && open >= close[1] && close <= open[1] {
+        return (body > prev_body)
+            .then(|| PatternSignal::new(Pattern::Engulfing, Direction::Bearish, strength));
+    }
+
+    None
+}
+
+/// 锤子线，前一根 k 线的收盘价低于再前两根的收盘价表示下跌。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn hammer(open: &Source, high: &Source, low: &Source, close: &Source) -> Option<PatternSignal> {
+    let [body, upper, lower, range] = shape(open, high, low, close, 0);
+
+    (range > 0.0
+        && body > 0.1 * range
+        && lower >= 2.0 * body
+        && upper <= 0.1 * range
+        && close[1] < close[3])
+        .then(|| PatternSignal::new(Pattern::Hammer, Direction::Bullish, lower / range))
+}
+
+/// 射击之星，前一根 k 线的收盘价高于再前两根的收盘价表示上涨。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn shooting_star(
+    open: &Source,
+    high: &Source,
+    low: &Source,
+    close: &Source,
+) -> Option<PatternSignal> {
+    let [body, upper, lower, range] = shape(open, high, low, close, 0);
+
+    (range > 0.0
+        && body > 0.1 * range
+        && upper >= 2.0 * body
+        && lower <= 0.1 * range
+        && close[1] > close[3])
+        .then(|| PatternSignal::new(Pattern::ShootingStar, Direction::Bearish, upper / range))
+}
+
+/// 十字星，实体小于等于振幅的 10%。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn doji(open: &Source, high: &Source, low: &Source, close: &Source) -> Option<PatternSignal> {
+    let [body, _, _, range] = shape(open, high, low, close, 0);
+
+    (range > 0.0 && body <= 0.1 * range).then(|| {
+        PatternSignal::new(
+            Pattern::Doji,
+            Direction::Neutral,
+            1.0 - body / (0.1 * range),
+        )
+    })
+}
+
+/// 启明星或者黄昏星。
+/// 第一根 k 线的实体大于等于振幅的一半，第二根 k 线的实体小于等于第一根的 30%，第三根 k 线的收盘价超过第一根实体的中点。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn star_pattern(
+    open: &Source,
+    high: &Source,
+    low: &Source,
+    close: &Source,
+) -> Option<PatternSignal> {
+    let [first_body, _, _, first_range] = shape(open, high, low, close, 2);
+    let [second_body, ..] = shape(open, high, low, close, 1);
+
+    if !(first_body >= 0.5 * first_range && second_body <= 0.3 * first_body) {
+        return None;
+    }
+
+    let middle = (open[2] + close[2]) / 2.0;
+    let strength = (close - middle) / (open[2] - middle);
+
+    if close[2] < open[2] && close > open && close > middle {
+        return Some(PatternSignal::new(
+            Pattern::MorningStar,
+            Direction::Bullish,
+            strength,
+        ));
+    }
+
+    if close[2] > open[2] && close < open && close < middle {
+        return Some(PatternSignal::new(
+            Pattern::EveningStar,
+            Direction::Bearish,
+            strength,
+        ));
+    }
+
+    None
+}
+
+/// 红三兵或者三只乌鸦。
+/// 连续三根同方向的 k 线，收盘价依次升高或者降低，开盘价都在前一根 k 线的实体之内。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn three_pattern(
+    open: &Source,
+    high: &Source,
+    low: &Source,
+    close: &Source,
+) -> Option<PatternSignal> {
+    let strength = (0..3)
+        .map(|i| {
+            let [body, _, _, range] = shape(open, high, low, close, i);
+            body / range
+        })
+        .sum::<f64>()
+        / 3.0;
+
+    if (0..3).all(|i| close[i] > open[i])
+        && (0..2)
+            .all(|i| close[i] > close[i + 1] && open[i] >= open[i + 1] && open[i] <= close[i + 1])
+    {
+        return Some(PatternSignal::new(
+            Pattern::ThreeWhiteSoldiers,
+            Direction::Bullish,
+            strength,
+        ));
+    }
+
+    if (0..3).all(|i| close[i] < open[i])
+        && (0..2)
+            .all(|i| close[i] < close[i + 1] && open[i] <= open[i + 1] && open[i] >= close[i + 1])
+    {
+        return Some(PatternSignal::new(
+            Pattern::ThreeBlackCrows,
+            Direction::Bearish,
+            strength,
+        ));
+    }
+
+    None
+}
+
+/// 内包线或者外包线。
+/// 内包线的方向为中性，外包线的方向为当前 k 线的方向。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn bar_pattern(
+    open: &Source,
+    high: &Source,
+    low: &Source,
+    close: &Source,
+) -> Option<PatternSignal> {
+    let [_, _, _, range] = shape(open, high, low, close, 0);
+    let [_, _, _, prev_range] = shape(open, high, low, close, 1);
+
+    if high < high[1] && low > low[1] {
+        return Some(PatternSignal::new(
+            Pattern::InsideBar,
+            Direction::Neutral,
+            1.0 - range / prev_range,
+        ));
+    }
+
+    if high > high[1] && low < low[1] {
+        let direction = if close > open {
+            Direction::Bullish
+        } else if close < open {
+            Direction::Bearish
+        } else {
+            Direction::Neutral
+        };
+
+        return Some(PatternSignal::new(
+            Pattern::OutsideBar,
+            direction,
+            1.0 - prev_range / range,
+        ));
+    }
+
+    None
+}
+
+/// 识别以当前 k 线结束的所有形态。
+///
+/// * `open` 开盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+/// * `return` 识别到的形态，同一根 k 线可能同时满足多个形态。
+pub fn patterns(open: &Source, high: &Source, low: &Source, close: &Source) -> Vec<PatternSignal> {
+    [
+        engulfing,
+        hammer,
+        shooting_star,
+        doji,
+        star_pattern,
+        three_pattern,
+        bar_pattern,
+    ]
+    .iter()
+    .filter_map(|f| f(open, high, low, close))
+    .collect()
+}
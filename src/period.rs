@@ -0,0 +1,313 @@
+//! 按照日，周，月，年统计收益，包括已经实现的盈亏和按照收盘价估算的盈亏，以及月度收益表。
+
+use crate::*;
+use chrono::Datelike;
+
+/// 统计周期。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Period {
+    /// 日。
+    Day,
+
+    /// 周，使用 ISO 周。
+    Week,
+
+    /// 月。
+    Month,
+
+    /// 年。
+    Year,
+}
+
+/// 一个周期的收益。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PeriodReturn {
+    /// 名称，例如 2023-08-25，2023-W34，2023-08，2023。
+    pub label: String,
+
+    /// 年份，周为 ISO 周的年份。
+    pub year: i32,
+
+    /// 日为一年中的第几天，周为 ISO 周数，月为月份，年为 0。
+    pub number: u32,
+
+    /// 周期内第一根 k 线的时间。
+    pub start_time: u64,
+
+    /// 周期内最后一根 k 线的时间。
+    pub end_time: u64,
+
+    /// 周期开始时的权益，即上一个周期结束时的权益。
+    pub start_equity: f64,
+
+    /// 周期结束时的权益。
+    pub end_equity: f64,
+
+    /// 已经实现的净收益，按照交易记录的时间统计。
+    pub realized: f64,
+
+    /// 按照收盘价估算的收益，即权益的变化。
+    pub profit: f64,
+
+    /// 收益率，收益除以周期开始时的权益。
+    pub profit_ratio: f64,
+}
+
+/// 按照周期统计收益。
+///
+/// * `k` k 线数据。
+/// * `history` 历史仓位。
+/// * `initial_margin` 初始保证金。
+/// * `period` 统计周期。
+/// * `offset` 时区偏移，单位分钟，例如东八区为 480。
+/// * `return` 每个周期的收益，旧的数据在前面。
+pub fn period_returns<A, B>(
+    k: A,
+    history: B,
+    initial_margin: f64,
+    period: Period,
+    offset: i32,
+) -> Vec<PeriodReturn>
+where
+    A: AsRef<[K]>,
+    B: AsRef<[Position]>,
+{
+    let k = k.as_ref();
+    let history = history.as_ref();
+    let equity = equity_curve(k, history, initial_margin);
+    let mut result = Vec::<PeriodReturn>::new();
+
+    for (v, equity) in k.iter().zip(equity).rev() {
+        let (label, year, number) = period_label(v.time, period, offset);
+
+        match result.last_mut() {
+            Some(last) if last.label == label => {
+                last.end_time = v.time;
+                last.end_equity = equity;
+            }
+            last => {
+                let start_equity = last.map_or(initial_margin, |v| v.end_equity);
+
+                result.push(PeriodReturn {
+                    label,
+                    year,
+                    number,
+                    start_time: v.time,
+                    end_time: v.time,
+                    start_equity,
+                    end_equity: equity,
+                    realized: 0.0,
+                    profit: 0.0,
+                    profit_ratio: 0.0,
+                });
+            }
+        }
+    }
+
+    for v in history.iter().flat_map(|v| &v.log) {
+        let index = result
+            .partition_point(|p| p.start_time <= v.time)
+            .saturating_sub(1);
+
+        if let Some(p) = result.get_mut(index) {
+            p.realized += v.profit - v.fee;
+        }
+    }
+
+    for v in result.iter_mut() {
+        v.profit = v.end_equity - v.start_equity;
+        v.profit_ratio = if v.start_equity == 0.0 {
+            0.0
+        } else {
+            v.profit / v.start_equity
+        };
+    }
+
+    result
+}
+
+/// 周期的名称，年份和期数。
+fn period_label(time: u64, period: Period, offset: i32) -> (String, i32, u32) {
+    let date = chrono::NaiveDateTime::from_timestamp_millis(time as i64).unwrap_or_default()
+        + chrono::Duration::minutes(offset as i64);
+
+    match period {
+        Period::Day => (
+            date.format("%Y-%m-%d").to_string(),
+            date.year(),
+            date.ordinal(),
+        ),
+        Period::Week => {
+            let week = date.iso_week();
+            (
+                format!("{}-W{:02}", week.year(), week.week()),
+                week.year(),
+                week.week(),
+            )
+        }
+        Period::Month => (date.format("%Y-%m").to_string(), date.year(), date.month()),
+        Period::Year => (date.year().to_string(), date.year(), 0),
+    }
+}
+
+/// 周期收益的统计。
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct PeriodStats {
+    /// 周期的数量。
+    pub count: usize,
+
+    /// 盈利的周期数量。
+    pub profitable: usize,
+
+    /// 盈利的周期占比。
+    pub profitable_ratio: f64,
+
+    /// 平均收益率。
+    pub average_ratio: f64,
+
+    /// 收益率最高的周期。
+    pub best: Option<PeriodReturn>,
+
+    /// 收益率最低的周期。
+    pub worst: Option<PeriodReturn>,
+}
+
+impl PeriodStats {
+    /// 统计周期收益。
+    ///
+    /// * `value` 周期收益，参考 [`period_returns`]。
+    pub fn new<T>(value: T) -> Self
+    where
+        T: AsRef<[PeriodReturn]>,
+    {
+        let value = value.as_ref();
+
+        if value.is_empty() {
+            return Self::default();
+        }
+
+        let profitable = value.iter().filter(|v| v.profit > 0.0).count();
+        let compare =
+            |a: &&PeriodReturn, b: &&PeriodReturn| a.profit_ratio.total_cmp(&b.profit_ratio);
+
+        Self {
+            count: value.len(),
+            profitable,
+            profitable_ratio: profitable as f64 / value.len() as f64,
+            average_ratio: value.iter().map(|v| v.profit_ratio).sum::<f64>() / value.len() as f64,
+            best: value.iter().max_by(compare).cloned(),
+            worst: value.iter().min_by(compare).cloned(),
+        }
+    }
+}
+
+/// 月度收益表的一行。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MonthlyReturn {
+    /// 年份。
+    pub year: i32,
+
+    /// 每个月的收益率，没有数据的月份为 None。
+    pub month: [Option<f64>; 12],
+
+    /// 全年的收益率，每个月的收益率复利计算。
+    pub total: f64,
+}
+
+/// 月度收益表，可以用于绘制热力图。
+///
+/// * `month` 按月统计的收益，参考 [`period_returns`]。
+/// * `return` 每年一行，旧的数据在前面。
+pub fn monthly_table<T>(month: T) -> Vec<MonthlyReturn>
+where
+    T: AsRef<[PeriodReturn]>,
+{
+    let mut result = Vec::<MonthlyReturn>::new();
+
+    for v in month.as_ref() {
+        if result.last().map(|last| last.year) != Some(v.year) {
+            result.push(MonthlyReturn {
+                year: v.year,
+                month: [None; 12],
+                total: 0.0,
+            });
+        }
+
+        let last = result.last_mut().unwrap();
+
+        if let Some(slot) = (v.number as usize)
+            .checked_sub(1)
+            .and_then(|i| last.month.get_mut(i))
+        {
+            *slot = Some(v.profit_ratio);
+        }
+
+        last.total = (1.0 + last.total) * (1.0 + v.profit_ratio) - 1.0;
+    }
+
+    result
+}
+
+/// 日，周，月，年的收益统计。
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Breakdown {
+    /// 时区偏移，单位分钟。
+    pub offset: i32,
+
+    /// 每日收益。
+    pub day: Vec<PeriodReturn>,
+
+    /// 每周收益。
+    pub week: Vec<PeriodReturn>,
+
+    /// 每月收益。
+    pub month: Vec<PeriodReturn>,
+
+    /// 每年收益。
+    pub year: Vec<PeriodReturn>,
+
+    /// 月度收益表。
+    pub monthly: Vec<MonthlyReturn>,
+}
+
+impl Breakdown {
+    /// 按照日，周，月，年统计收益。
+    ///
+    /// * `k` k 线数据。
+    /// * `history` 历史仓位。
+    /// * `initial_margin` 初始保证金。
+    /// * `offset` 时区偏移，单位分钟，例如东八区为 480，参考 [`local_offset`]。
+    pub fn new(k: &[K], history: &[Position], initial_margin: f64, offset: i32) -> Self {
+        let period = |period| period_returns(k, history, initial_margin, period, offset);
+        let month = period(Period::Month);
+
+        Self {
+            offset,
+            day: period(Period::Day),
+            week: period(Period::Week),
+            monthly: monthly_table(&month),
+            month,
+            year: period(Period::Year),
+        }
+    }
+
+    /// 获取周期收益。
+    pub fn get(&self, period: Period) -> &[PeriodReturn] {
+        match period {
+            Period::Day => &self.day,
+            Period::Week => &self.week,
+            Period::Month => &self.month,
+            Period::Year => &self.year,
+        }
+    }
+
+    /// 周期收益的统计，例如盈利月份的占比。
+    pub fn stats(&self, period: Period) -> PeriodStats {
+        PeriodStats::new(self.get(period))
+    }
+}
+
+/// 本地时区的偏移，单位分钟。
+pub fn local_offset() -> i32 {
+    chrono::Local::now().offset().local_minus_utc() / 60
+}
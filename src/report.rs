@@ -0,0 +1,611 @@
+//! 回测报告，生成一个离线的 html 文件，包含 k 线，买卖点，止盈止损和强平标记，持仓区间，
+//! 权益和回撤，自定义的指标，可以排序的交易列表，统计指标和月度收益表。
+
+use crate::*;
+use std::collections::HashMap;
+
+/// 回测的统计指标。
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Summary {
+    /// 初始保证金。
+    pub initial_margin: f64,
+
+    /// 最终权益。
+    pub final_equity: f64,
+
+    /// 净收益，扣除手续费。
+    pub profit: f64,
+
+    /// 净收益率，净收益除以初始保证金，初始保证金为 0 时为 0。
+    pub profit_ratio: f64,
+
+    /// 手续费。
+    pub fee: f64,
+
+    /// 交易次数，一个历史仓位为一次交易。
+    pub trade: usize,
+
+    /// 盈利次数。
+    pub win: usize,
+
+    /// 亏损次数。
+    pub loss: usize,
+
+    /// 胜率。
+    pub win_rate: f64,
+
+    /// 盈利因子，总盈利除以总亏损。
+    pub profit_factor: f64,
+
+    /// 平均每次交易的净收益。
+    pub average_profit: f64,
+
+    /// 最大单次盈利。
+    pub max_profit: f64,
+
+    /// 最大单次亏损。
+    pub max_loss: f64,
+
+    /// 最大回撤，权益从最高点下跌的最大比例。
+    pub max_drawdown: f64,
+}
+
+impl Summary {
+    /// 计算统计指标。
+    ///
+    /// * `k` k 线数据，用于计算权益和回撤。
+    /// * `history` 历史仓位。
+    /// * `initial_margin` 初始保证金。
+    pub fn new(k: &[K], history: &[Position], initial_margin: f64) -> Self {
+        let net = history.iter().map(|v| v.profit - v.fee).collect::<Vec<_>>();
+        let profit = net.iter().sum::<f64>();
+        let win = net.iter().filter(|v| **v > 0.0).count();
+        let loss = net.iter().filter(|v| **v < 0.0).count();
+        let gross_profit = net.iter().filter(|v| **v > 0.0).sum::<f64>();
+        let gross_loss = -net.iter().filter(|v| **v < 0.0).sum::<f64>();
+        let ratio = |a: f64, b: f64| if b == 0.0 { 0.0 } else { a / b };
+
+        Self {
+            initial_margin,
+            final_equity: initial_margin + profit,
+            profit,
+            profit_ratio: ratio(profit, initial_margin),
+            fee: history.iter().map(|v| v.fee).sum(),
+            trade: history.len(),
+            win,
+            loss,
+            win_rate: ratio(win as f64, history.len() as f64),
+            profit_factor: ratio(gross_profit, gross_loss),
+            average_profit: ratio(profit, history.len() as f64),
+            max_profit: net.iter().copied().fold(0.0, f64::max),
+            max_loss: net.iter().copied().fold(0.0, f64::min),
+            max_drawdown: -drawdown(equity_curve(k, history, initial_margin))
+                .into_iter()
+                .fold(0.0, f64::min),
+        }
+    }
+}
+
+/// 每根 k 线收盘时的权益，已经实现的净收益加上按照收盘价估算的未实现盈亏。
+///
+/// * `k` k 线数据。
+/// * `history` 历史仓位。
+/// * `initial_margin` 初始保证金。
+/// * `return` 权益，新的数据在前面，和 `k` 对齐。
+pub fn equity_curve<A, B>(k: A, history: B, initial_margin: f64) -> Vec<f64>
+where
+    A: AsRef<[K]>,
+    B: AsRef<[Position]>,
+{
+    let k = k.as_ref();
+
+    if k.is_empty() {
+        return Vec::new();
+    }
+
+    // 旧的数据在前面
+    let time = k.iter().rev().map(|v| v.time).collect::<Vec<_>>();
+    let close = k.iter().rev().map(|v| v.close).collect::<Vec<_>>();
+    let index = |t: u64| time.partition_point(|v| *v <= t).saturating_sub(1);
+    let mut realized = vec![0.0; k.len()];
+    let mut unrealized = vec![0.0; k.len()];
+
+    for position in history.as_ref() {
+        let direction = if position.side == Side::BuyLong {
+            1.0
+        } else {
+            -1.0
+        };
+        let end = if position.close_time == 0 {
+            k.len() - 1
+        } else {
+            index(position.close_time)
+        };
+        let mut record = position.log.iter().peekable();
+        let mut quantity = 0.0;
+        let mut cost = 0.0;
+
+        for i in index(position.open_time)..=end {
+            while let Some(v) = record.next_if(|v| i == end || index(v.time) <= i) {
+                realized[i] += v.profit - v.fee;
+
+                if v.side == Side::BuyLong || v.side == Side::SellShort {
+                    quantity += v.quantity;
+                    cost += v.price * v.quantity;
+                } else {
+                    let price = cost / quantity;
+                    quantity = (quantity - v.quantity).max(0.0);
+                    cost = price * quantity;
+                }
+            }
+
+            if quantity > 0.0 {
+                unrealized[i] += (close[i] - cost / quantity) * quantity * direction;
+            }
+        }
+    }
+
+    let mut sum = initial_margin;
+
+    realized
+        .iter()
+        .zip(unrealized)
+        .map(|(a, b)| {
+            sum += a;
+            sum + b
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// 回撤，权益相对于之前最高点的跌幅，0 表示处于最高点。
+///
+/// * `equity` 权益，新的数据在前面，参考 [`equity_curve`]。
+/// * `return` 回撤，小于等于 0，新的数据在前面。
+pub fn drawdown<T>(equity: T) -> Vec<f64>
+where
+    T: AsRef<[f64]>,
+{
+    let mut peak = f64::NAN;
+
+    equity
+        .as_ref()
+        .iter()
+        .rev()
+        .map(|v| {
+            peak = peak.max(*v);
+
+            if peak > 0.0 {
+                v / peak - 1.0
+            } else {
+                0.0
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// 交易记录的成交原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Exit {
+    /// 普通委托。
+    Order,
+
+    /// 止盈委托。
+    StopProfit,
+
+    /// 止损委托。
+    StopLoss,
+
+    /// 强平。
+    Liquidation,
+}
+
+/// 根据撮合引擎的事件找出止盈，止损和强平的交易记录。
+///
+/// * `event` 撮合引擎的事件。
+/// * `return` 交易记录的时间和价格对应的成交原因。
+fn exit_map(event: &[Event]) -> HashMap<(u64, u64), Exit> {
+    let mut delegate = HashMap::new();
+    let mut result = HashMap::new();
+    let key = |v: &Record| (v.time, v.price.to_bits());
+
+    for i in event {
+        match i {
+            Event::Accept {
+                id, delegate: v, ..
+            }
+            | Event::Amend {
+                id, delegate: v, ..
+            } => {
+                let (profit, loss) = match *v {
+                    DelegateState::HedgingProfit(.., a) | DelegateState::OpenProfit(.., a) => {
+                        (Some(a), None)
+                    }
+                    DelegateState::HedgingLoss(.., b) | DelegateState::OpenLoss(.., b) => {
+                        (None, Some(b))
+                    }
+                    DelegateState::HedgingProfitLoss(.., a, b)
+                    | DelegateState::OpenProfitLoss(.., a, b)
+                    | DelegateState::ProfitLoss(a, b) => (Some(a), Some(b)),
+                    _ => continue,
+                };
+
+                delegate.insert(*id, (profit, loss));
+            }
+            Event::Fill { id, record, .. } => {
+                let Some((profit, loss)) = delegate.get(id) else {
+                    continue;
+                };
+
+                let matched = |v: &Option<Delegate>| {
+                    v.is_some_and(|v| {
+                        let (a, b) = match v.price {
+                            Price::GreaterThanMarket(a) | Price::LessThanMarket(a) => (a, a),
+                            Price::GreaterThanLimit(a, b) | Price::LessThanLimit(a, b) => (a, b),
+                        };
+
+                        v.side == record.side && (record.price == a || record.price == b)
+                    })
+                };
+
+                if matched(profit) {
+                    result.insert(key(record), Exit::StopProfit);
+                } else if matched(loss) {
+                    result.insert(key(record), Exit::StopLoss);
+                }
+            }
+            Event::Liquidate { position, .. } => {
+                if let Some(v) = position.log.last() {
+                    result.insert(key(v), Exit::Liquidation);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// 指标线。
+#[derive(Debug, Clone, serde::Serialize)]
+struct Line {
+    name: String,
+
+    /// 旧的数据在前面，NaN 为 null。
+    value: Vec<Option<f64>>,
+}
+
+/// 买卖点。
+#[derive(Debug, Clone, serde::Serialize)]
+struct Mark {
+    time: u64,
+    price: f64,
+    side: Side,
+    exit: Exit,
+    text: String,
+}
+
+/// 持仓区间。
+#[derive(Debug, Clone, serde::Serialize)]
+struct Span {
+    open_time: u64,
+    open_price: f64,
+    close_time: u64,
+    close_price: f64,
+    profit: f64,
+}
+
+/// 交易列表的一行。
+#[derive(Debug, Clone, serde::Serialize)]
+struct Trade {
+    product: String,
+    side: Side,
+    open_time: u64,
+    close_time: u64,
+    open_price: f64,
+    close_price: f64,
+    quantity: f64,
+    margin: f64,
+    profit: f64,
+    fee: f64,
+    net: f64,
+    profit_ratio: f64,
+    exit: Exit,
+}
+
+/// 注入到模板的数据。
+#[derive(Debug, Clone, serde::Serialize)]
+struct Data<'a> {
+    title: &'a str,
+    k: Vec<&'a K>,
+    equity: Vec<Option<f64>>,
+    drawdown: Vec<Option<f64>>,
+    overlay: &'a [Line],
+    pane: &'a [(String, Vec<Line>)],
+    mark: Vec<Mark>,
+    span: Vec<Span>,
+    trade: Vec<Trade>,
+    summary: Summary,
+    monthly: Vec<MonthlyReturn>,
+    period: Vec<(Period, PeriodStats)>,
+}
+
+/// 回测报告。
+///
+/// ```
+/// use auto_trading::*;
+///
+/// let k = Vec::<K>::new();
+/// let history = Vec::<Position>::new();
+/// let html = Report::new(&k, &history)
+///     .initial_margin(1000.0)
+///     .overlay("sma", Vec::new())
+///     .pane("rsi", "rsi", Vec::new())
+///     .to_html();
+/// assert!(html.contains("<html"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    k: &'a [K],
+    history: &'a [Position],
+    event: &'a [Event],
+    initial_margin: f64,
+    offset: i32,
+    title: String,
+    overlay: Vec<Line>,
+    pane: Vec<(String, Vec<Line>)>,
+}
+
+impl<'a> Report<'a> {
+    /// 创建回测报告。
+    ///
+    /// * `k` k 线数据。
+    /// * `history` 历史仓位。
+    pub fn new(k: &'a [K], history: &'a [Position]) -> Self {
+        Self {
+            k,
+            history,
+            event: &[],
+            initial_margin: 0.0,
+            offset: 0,
+            title: "backtest".to_string(),
+            overlay: Vec::new(),
+            pane: Vec::new(),
+        }
+    }
+
+    /// 撮合引擎的事件，用于标记止盈，止损和强平，参考 [`Backtester::start_strategy`]。
+    pub fn event(mut self, value: &'a [Event]) -> Self {
+        self.event = value;
+        self
+    }
+
+    /// 初始保证金，用于计算权益和收益率。
+    pub fn initial_margin(mut self, value: f64) -> Self {
+        self.initial_margin = value;
+        self
+    }
+
+    /// 统计周期收益使用的时区偏移，单位分钟，默认为 0，即 UTC，这样同一个回测在不同的机器上生成的报告相同。
+    /// 使用本地时区可以传入 [`local_offset`]，参考 [`Breakdown`]。
+    pub fn timezone(mut self, offset: i32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// 标题。
+    pub fn title<S>(mut self, value: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.title = value.as_ref().to_string();
+        self
+    }
+
+    /// 叠加在 k 线上的指标，例如均线。
+    ///
+    /// * `name` 名称。
+    /// * `value` 指标的值，新的数据在前面，和 k 线对齐。
+    pub fn overlay<S, T>(mut self, name: S, value: T) -> Self
+    where
+        S: AsRef<str>,
+        T: AsRef<[f64]>,
+    {
+        let line = self.line(name, value);
+        self.overlay.push(line);
+        self
+    }
+
+    /// 在 k 线下方单独显示的指标，同一个窗格可以有多条指标线。
+    ///
+    /// * `pane` 窗格的名称。
+    /// * `name` 名称。
+    /// * `value` 指标的值，新的数据在前面，和 k 线对齐。
+    pub fn pane<P, S, T>(mut self, pane: P, name: S, value: T) -> Self
+    where
+        P: AsRef<str>,
+        S: AsRef<str>,
+        T: AsRef<[f64]>,
+    {
+        let line = self.line(name, value);
+
+        match self.pane.iter_mut().find(|v| v.0 == pane.as_ref()) {
+            Some(v) => v.1.push(line),
+            None => self.pane.push((pane.as_ref().to_string(), vec![line])),
+        }
+
+        self
+    }
+
+    /// 统计指标。
+    pub fn summary(&self) -> Summary {
+        Summary::new(self.k, self.history, self.initial_margin)
+    }
+
+    /// 按照日，周，月，年统计收益。
+    pub fn breakdown(&self) -> Breakdown {
+        Breakdown::new(self.k, self.history, self.initial_margin, self.offset)
+    }
+
+    /// 生成 html 文本，不依赖网络。
+    pub fn to_html(&self) -> String {
+        let equity = equity_curve(self.k, self.history, self.initial_margin);
+        let exit = exit_map(self.event);
+        let breakdown = self.breakdown();
+        let k = self.k.iter().rev().collect::<Vec<_>>();
+
+        // 对齐到所在的 k 线
+        let align = |t: u64| {
+            k.get(k.partition_point(|v| v.time <= t).saturating_sub(1))
+                .map_or(t, |v| v.time)
+        };
+
+        let mut mark = Vec::new();
+        let mut span = Vec::new();
+        let mut trade = Vec::new();
+
+        for p in self.history {
+            let mut last = Exit::Order;
+
+            for (index, i) in p.log.iter().enumerate() {
+                let kind = exit
+                    .get(&(i.time, i.price.to_bits()))
+                    .copied()
+                    .unwrap_or(Exit::Order);
+                let text = match kind {
+                    Exit::StopProfit => "StopProfit",
+                    Exit::StopLoss => "StopLoss",
+                    Exit::Liquidation => "Liquidation",
+                    Exit::Order => match i.side {
+                        Side::BuyLong | Side::SellShort if index == 0 => "Open",
+                        Side::BuyLong | Side::SellShort => "+1",
+                        _ if index == p.log.len() - 1 => "Close",
+                        _ => "-1",
+                    },
+                };
+
+                mark.push(Mark {
+                    time: align(i.time),
+                    price: i.price,
+                    side: i.side,
+                    exit: kind,
+                    text: format!("{:?} {}", i.side, text),
+                });
+                last = kind;
+            }
+
+            span.push(Span {
+                open_time: align(p.open_time),
+                open_price: p.open_price,
+                close_time: align(p.close_time),
+                close_price: p.close_price,
+                profit: p.profit - p.fee,
+            });
+
+            trade.push(Trade {
+                product: p.product.clone(),
+                side: p.side,
+                open_time: p.open_time,
+                close_time: p.close_time,
+                open_price: p.open_price,
+                close_price: p.close_price,
+                quantity: p.quantity,
+                margin: p.margin,
+                profit: p.profit,
+                fee: p.fee,
+                net: p.profit - p.fee,
+                profit_ratio: p.profit_ratio,
+                exit: last,
+            });
+        }
+
+        let data = Data {
+            title: &self.title,
+            equity: to_option(equity.iter().rev().copied()),
+            drawdown: to_option(drawdown(&equity).into_iter().rev()),
+            k,
+            overlay: &self.overlay,
+            pane: &self.pane,
+            mark,
+            span,
+            trade,
+            summary: self.summary(),
+            monthly: breakdown.monthly.clone(),
+            period: [Period::Day, Period::Week, Period::Month, Period::Year]
+                .into_iter()
+                .map(|v| (v, breakdown.stats(v)))
+                .collect(),
+        };
+
+        // 避免数据中的 </script> 结束脚本
+        let json = serde_json::to_string(&data).unwrap().replace("</", "<\\/");
+
+        render(
+            include_str!("../report.txt"),
+            &[
+                ("{title}", &html_escape(&self.title)),
+                ("{report}", &json),
+                ("{klinecharts}", include_str!("../klinecharts.js")),
+            ],
+        )
+    }
+
+    fn line<S, T>(&self, name: S, value: T) -> Line
+    where
+        S: AsRef<str>,
+        T: AsRef<[f64]>,
+    {
+        let value = value.as_ref();
+
+        // 新的数据在前面，超出 k 线的部分丢弃，不足的部分为 null
+        Line {
+            name: name.as_ref().to_string(),
+            value: to_option(
+                (0..self.k.len())
+                    .rev()
+                    .map(|i| value.get(i).copied().unwrap_or(f64::NAN)),
+            ),
+        }
+    }
+}
+
+fn to_option<T>(iter: T) -> Vec<Option<f64>>
+where
+    T: IntoIterator<Item = f64>,
+{
+    iter.into_iter()
+        .map(|v| if v.is_finite() { Some(v) } else { None })
+        .collect()
+}
+
+/// 一次性替换模板中的占位符，替换进去的内容不会再被替换。
+fn render(template: &str, value: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some((index, (key, value))) = value
+        .iter()
+        .filter_map(|v| rest.find(v.0).map(|index| (index, v)))
+        .min_by_key(|v| v.0)
+    {
+        result.push_str(&rest[..index]);
+        result.push_str(value);
+        rest = &rest[index + key.len()..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
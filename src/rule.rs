@@ -0,0 +1,624 @@
+//! 规则策略，使用表达式描述开仓和平仓的条件，从 TOML 或者 JSON 配置文件加载，不需要编写和编译代码。
+//!
+//! 表达式支持：
+//! * 数字，例如 `-350`，`0.5`。
+//! * 数据系列 `open`，`high`，`low`，`close`，`close[1]` 表示前一根 k 线的收盘价。
+//! * 算术运算 `+ - * / %`，比较运算 `< <= > >= == !=`，逻辑运算 `&& || !`，括号。
+//! * 指标函数，第一个参数是数据系列，其他参数是数字，例如 `cci(close, 20)`，`ema(close[1], 10)`，参考 [`Rule`]。
+//!
+//! 比较和逻辑运算的结果 1 表示真，0 表示假。
+
+use crate::*;
+
+/// 数据系列。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Open,
+    High,
+    Low,
+    Close,
+}
+
+/// 表达式。
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    /// 数字。
+    Number(f64),
+
+    /// 数据系列，前面第几根 k 线。
+    Series(Field, usize),
+
+    /// 取反或者逻辑非。
+    Unary(&'static str, Box<Expr>),
+
+    /// 二元运算。
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+
+    /// 函数调用。
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+const SYMBOL: [&str; 18] = [
+    "&&", "||", "<=", ">=", "==", "!=", "<", ">", "+", "-", "*", "/", "%", "!", "(", ")", "[", "]",
+];
+
+fn tokenize(text: &str) -> anyhow::Result<Vec<Token>> {
+    let mut result = Vec::new();
+    let mut rest = text.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|v: char| !(v.is_ascii_digit() || v == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| anyhow::anyhow!("rule {}: invalid number: {}", text, &rest[..end]))?;
+            result.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest
+                .find(|v: char| !(v.is_ascii_alphanumeric() || v == '_'))
+                .unwrap_or(rest.len());
+            result.push(Token::Ident(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if c == ',' {
+            result.push(Token::Symbol(","));
+            rest = &rest[1..];
+        } else {
+            let symbol = SYMBOL
+                .iter()
+                .find(|v| rest.starts_with(**v))
+                .ok_or_else(|| anyhow::anyhow!("rule {}: unexpected character: {}", text, c))?;
+            result.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        }
+
+        rest = rest.trim_start();
+    }
+
+    Ok(result)
+}
+
+/// 递归下降解析，优先级从低到高为 `||`，`&&`，比较，加减，乘除，一元运算。
+struct Parser<'a> {
+    text: &'a str,
+    token: Vec<Token>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.token.get(self.index)
+    }
+
+    fn symbol(&mut self, symbol: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Symbol(v)) if symbol.contains(v) => {
+                let v = *v;
+                self.index += 1;
+                Some(v)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> anyhow::Result<()> {
+        match self.symbol(&[symbol]) {
+            Some(_) => Ok(()),
+            None => anyhow::bail!(
+                "rule {}: expected {} but found {:?}",
+                self.text,
+                symbol,
+                self.peek()
+            ),
+        }
+    }
+
+    fn binary<F>(&mut self, symbol: &[&'static str], mut next: F) -> anyhow::Result<Expr>
+    where
+        F: FnMut(&mut Self) -> anyhow::Result<Expr>,
+    {
+        let mut result = next(self)?;
+
+        while let Some(op) = self.symbol(symbol) {
+            result = Expr::Binary(op, Box::new(result), Box::new(next(self)?));
+        }
+
+        Ok(result)
+    }
+
+    fn or(&mut self) -> anyhow::Result<Expr> {
+        self.binary(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> anyhow::Result<Expr> {
+        self.binary(&["&&"], Self::compare)
+    }
+
+    fn compare(&mut self) -> anyhow::Result<Expr> {
+        self.binary(&["<=", ">=", "==", "!=", "<", ">"], Self::add)
+    }
+
+    fn add(&mut self) -> anyhow::Result<Expr> {
+        self.binary(&["+", "-"], Self::mul)
+    }
+
+    fn mul(&mut self) -> anyhow::Result<Expr> {
+        self.binary(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> anyhow::Result<Expr> {
+        match self.symbol(&["-", "!"]) {
+            Some(op) => match (op, self.unary()?) {
+                ("-", Expr::Number(v)) => Ok(Expr::Number(-v)),
+                (op, v) => Ok(Expr::Unary(op, Box::new(v))),
+            },
+            None => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> anyhow::Result<Expr> {
+        let token = self.peek().cloned();
+        self.index += 1;
+
+        match token {
+            Some(Token::Number(v)) => Ok(Expr::Number(v)),
+            Some(Token::Symbol("(")) => {
+                let result = self.or()?;
+                self.expect(")")?;
+                Ok(result)
+            }
+            Some(Token::Ident(name)) => {
+                let field = match name.as_str() {
+                    "open" => Some(Field::Open),
+                    "high" => Some(Field::High),
+                    "low" => Some(Field::Low),
+                    "close" => Some(Field::Close),
+                    _ => None,
+                };
+
+                if let Some(field) = field {
+                    if self.symbol(&["["]).is_none() {
+                        return Ok(Expr::Series(field, 0));
+                    }
+
+                    let index = match self.peek() {
+                        Some(Token::Number(v)) if valid_integer(*v, 0.0) => *v as usize,
+                        v => anyhow::bail!("rule {}: invalid index: {:?}", self.text, v),
+                    };
+                    self.index += 1;
+                    self.expect("]")?;
+                    return Ok(Expr::Series(field, index));
+                }
+
+                self.expect("(")?;
+                let mut args = Vec::new();
+
+                if self.symbol(&[")"]).is_none() {
+                    loop {
+                        args.push(self.or()?);
+
+                        if self.symbol(&[")"]).is_some() {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                check_call(self.text, &name, &args)?;
+                Ok(Expr::Call(name, args))
+            }
+            v => anyhow::bail!("rule {}: unexpected token: {:?}", self.text, v),
+        }
+    }
+}
+
+/// 函数的参数，`s` 表示数据系列，`n` 表示正整数，`e` 表示表达式。
+fn signature(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "sma" | "ema" | "rma" | "cci" | "rsi" | "highest" | "lowest" | "wma" | "hma" | "stdev" => {
+            "sn"
+        }
+        "dif" | "dea" | "macd" => "snnn",
+        "tr" => "",
+        "atr" | "williams_r" => "n",
+        "crossover" | "crossunder" | "min" | "max" => "ee",
+        "abs" => "e",
+        _ => return None,
+    })
+}
+
+fn check_call(text: &str, name: &str, args: &[Expr]) -> anyhow::Result<()> {
+    let signature = signature(name)
+        .ok_or_else(|| anyhow::anyhow!("rule {}: unknown function: {}", text, name))?;
+
+    anyhow::ensure!(
+        signature.len() == args.len(),
+        "rule {}: function {} expects {} arguments",
+        text,
+        name,
+        signature.len()
+    );
+
+    for (kind, arg) in signature.chars().zip(args.iter()) {
+        let valid = match (kind, arg) {
+            ('s', Expr::Series(..)) => true,
+            ('n', Expr::Number(v)) => valid_integer(*v, 1.0),
+            ('e', _) => true,
+            _ => false,
+        };
+
+        anyhow::ensure!(
+            valid,
+            "rule {}: function {}: invalid argument: {:?}",
+            text,
+            name,
+            arg
+        );
+    }
+
+    Ok(())
+}
+
+/// 下标和周期必须是不小于 `min` 并且不超过 [`u32::MAX`] 的整数，避免转换为 usize 时饱和，计算下标时溢出。
+fn valid_integer(value: f64, min: f64) -> bool {
+    value.fract() == 0.0 && value >= min && value <= u32::MAX as f64
+}
+
+fn length(expr: &Expr) -> usize {
+    match expr {
+        Expr::Number(v) => *v as usize,
+        _ => 0,
+    }
+}
+
+fn truth(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// 规则，一个可以对 [`Context`] 求值的表达式。
+///
+/// 支持的函数：
+/// * `sma`，`ema`，`rma`，`cci`，`rsi`，`highest`，`lowest`，`wma`，`hma`，`stdev`，参数为数据系列和长度，例如 `rsi(close, 14)`。
+/// * `dif`，`dea`，`macd`，参数为数据系列，快线长度，慢线长度，dea 长度，参考 [`macd`]。
+/// * `tr()`，`atr(length)`，`williams_r(length)`，使用最高价，最低价和收盘价。
+/// * `crossover(a, b)`，`crossunder(a, b)`，`a` 上穿或者下穿 `b`。
+/// * `abs(a)`，`min(a, b)`，`max(a, b)`。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    text: String,
+    expr: Expr,
+}
+
+impl Rule {
+    /// 解析规则。
+    ///
+    /// * `text` 表达式，例如 `cci(close, 20) <= -350`。
+    pub fn parse<S>(text: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let text = text.as_ref();
+        let mut parser = Parser {
+            text,
+            token: tokenize(text)?,
+            index: 0,
+        };
+        let expr = parser.or()?;
+
+        anyhow::ensure!(
+            parser.index == parser.token.len(),
+            "rule {}: unexpected token: {:?}",
+            text,
+            parser.peek()
+        );
+
+        Ok(Self {
+            text: text.to_string(),
+            expr,
+        })
+    }
+
+    /// 表达式文本。
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 计算表达式的值，数据不足时为 NaN。
+    pub fn eval(&self, cx: &Context) -> f64 {
+        eval(&self.expr, cx, 0)
+    }
+
+    /// 表达式是否为真，非 0 并且不是 NaN 表示真。
+    pub fn test(&self, cx: &Context) -> bool {
+        let value = self.eval(cx);
+        value != 0.0 && !value.is_nan()
+    }
+}
+
+/// 计算表达式在 `offset` 根 k 线之前的值。
+fn eval(expr: &Expr, cx: &Context, offset: usize) -> f64 {
+    let series = |expr: &Expr| -> &Source {
+        match expr {
+            Expr::Series(field, index) => {
+                let source = match field {
+                    Field::Open => cx.open,
+                    Field::High => cx.high,
+                    Field::Low => cx.low,
+                    Field::Close => cx.close,
+                };
+                &source[offset.saturating_add(*index)..]
+            }
+            _ => Source::new(&[]),
+        }
+    };
+
+    match expr {
+        Expr::Number(v) => *v,
+        Expr::Series(..) => series(expr)[0],
+        Expr::Unary(op, v) => {
+            let v = eval(v, cx, offset);
+
+            match *op {
+                "-" => -v,
+                _ => truth(v == 0.0),
+            }
+        }
+        Expr::Binary(op, a, b) => {
+            let a = eval(a, cx, offset);
+            let b = eval(b, cx, offset);
+
+            match *op {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" => a / b,
+                "%" => a % b,
+                "<" => truth(a < b),
+                "<=" => truth(a <= b),
+                ">" => truth(a > b),
+                ">=" => truth(a >= b),
+                "==" => truth(a == b),
+                "!=" => truth(a != b),
+                "&&" => truth(a != 0.0 && !a.is_nan() && b != 0.0 && !b.is_nan()),
+                _ => truth((a != 0.0 && !a.is_nan()) || (b != 0.0 && !b.is_nan())),
+            }
+        }
+        Expr::Call(name, args) => {
+            let high = &cx.high[offset..];
+            let low = &cx.low[offset..];
+            let close = &cx.close[offset..];
+
+            match name.as_str() {
+                "sma" => sma(series(&args[0]), length(&args[1])),
+                "ema" => ema(series(&args[0]), length(&args[1])),
+                "rma" => rma(series(&args[0]), length(&args[1])),
+                "cci" => cci(series(&args[0]), length(&args[1])),
+                "rsi" => rsi(series(&args[0]), length(&args[1])),
+                "highest" => highest(series(&args[0]), length(&args[1])),
+                "lowest" => lowest(series(&args[0]), length(&args[1])),
+                "wma" => wma(series(&args[0]), length(&args[1])),
+                "hma" => hma(series(&args[0]), length(&args[1])),
+                "stdev" => stdev(series(&args[0]), length(&args[1])),
+                "dif" | "dea" | "macd" => {
+                    let result = macd(
+                        series(&args[0]),
+                        length(&args[1]),
+                        length(&args[2]),
+                        length(&args[3]),
+                    );
+
+                    match name.as_str() {
+                        "dif" => result.0,
+                        "dea" => result.1,
+                        _ => result.2,
+                    }
+                }
+                "tr" => tr(high, low, close),
+                "atr" => atr(high, low, close, length(&args[0])),
+                "williams_r" => williams_r(close, high, low, length(&args[0])),
+                "crossover" | "crossunder" => {
+                    let a = eval(&args[0], cx, offset);
+                    let b = eval(&args[1], cx, offset);
+                    let prev_a = eval(&args[0], cx, offset + 1);
+                    let prev_b = eval(&args[1], cx, offset + 1);
+
+                    if name == "crossover" {
+                        truth(a > b && prev_a <= prev_b)
+                    } else {
+                        truth(a < b && prev_a >= prev_b)
+                    }
+                }
+                "min" => eval(&args[0], cx, offset).min(eval(&args[1], cx, offset)),
+                "max" => eval(&args[0], cx, offset).max(eval(&args[1], cx, offset)),
+                _ => eval(&args[0], cx, offset).abs(),
+            }
+        }
+    }
+}
+
+fn unit_ignore() -> Unit {
+    Unit::Ignore
+}
+
+/// 规则策略的配置。
+///
+/// ```toml
+/// product = "BTC-USDT-SWAP"
+/// level = "Hour4"
+/// entry_long = "cci(close, 20) <= -350"
+/// exit_long = "cci(close, 20) >= 100"
+/// stop_loss_condition = { Proportion = 0.05 }
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RuleConfig {
+    /// 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+    pub product: String,
+
+    /// 策略的时间级别，即调用策略的时间周期。
+    pub level: Level,
+
+    /// 撮合使用的 k 线时间级别，None 表示和 `level` 相同，参考 [`Backtester::start_amplifier`]。
+    #[serde(default)]
+    pub k_level: Option<Level>,
+
+    /// 没有仓位时开多的条件。
+    #[serde(default)]
+    pub entry_long: Option<String>,
+
+    /// 持有多仓时平多的条件。
+    #[serde(default)]
+    pub exit_long: Option<String>,
+
+    /// 没有仓位时开空的条件，同时满足开多条件时只开多。
+    #[serde(default)]
+    pub entry_short: Option<String>,
+
+    /// 持有空仓时平空的条件。
+    #[serde(default)]
+    pub exit_short: Option<String>,
+
+    /// 开仓数量，参考 [`Context::order_condition`]。
+    #[serde(default = "unit_ignore")]
+    pub quantity: Unit,
+
+    /// 开仓保证金，参考 [`Context::order_condition`]。
+    #[serde(default = "unit_ignore")]
+    pub margin: Unit,
+
+    /// 止盈触发价格，[`Unit::Proportion`] 表示相对开仓价格的比例。
+    #[serde(default = "unit_ignore")]
+    pub stop_profit_condition: Unit,
+
+    /// 止损触发价格，[`Unit::Proportion`] 表示相对开仓价格的比例。
+    #[serde(default = "unit_ignore")]
+    pub stop_loss_condition: Unit,
+
+    /// 止盈委托价格，[`Unit::Ignore`] 表示市价。
+    #[serde(default = "unit_ignore")]
+    pub stop_profit: Unit,
+
+    /// 止损委托价格，[`Unit::Ignore`] 表示市价。
+    #[serde(default = "unit_ignore")]
+    pub stop_loss: Unit,
+}
+
+impl RuleConfig {
+    /// 从 TOML 文本解析配置。
+    pub fn from_toml<S>(text: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Ok(toml::from_str(text.as_ref())?)
+    }
+
+    /// 从 JSON 文本解析配置。
+    pub fn from_json<S>(text: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Ok(serde_json::from_str(text.as_ref())?)
+    }
+
+    /// 读取配置文件，扩展名为 json 时按照 JSON 解析，否则按照 TOML 解析。
+    ///
+    /// * `path` 文件路径。
+    pub fn load<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("read {}: {}", path.display(), e))?;
+
+        if path.extension().is_some_and(|v| v == "json") {
+            Self::from_json(text)
+        } else {
+            Self::from_toml(text)
+        }
+    }
+
+    /// 编译成策略，解析所有的规则。
+    pub fn compile(&self) -> anyhow::Result<RuleStrategy> {
+        let parse = |v: &Option<String>| v.as_ref().map(Rule::parse).transpose();
+
+        Ok(RuleStrategy {
+            config: self.clone(),
+            entry_long: parse(&self.entry_long)?,
+            exit_long: parse(&self.exit_long)?,
+            entry_short: parse(&self.entry_short)?,
+            exit_short: parse(&self.exit_short)?,
+        })
+    }
+}
+
+/// 规则策略，参考 [`RuleConfig`]。
+/// 没有仓位时依次检查开多和开空的条件，持有仓位时检查对应方向的平仓条件，都使用市价委托。
+#[derive(Debug, Clone)]
+pub struct RuleStrategy {
+    config: RuleConfig,
+    entry_long: Option<Rule>,
+    exit_long: Option<Rule>,
+    entry_short: Option<Rule>,
+    exit_short: Option<Rule>,
+}
+
+impl RuleStrategy {
+    /// 配置。
+    pub fn config(&self) -> &RuleConfig {
+        &self.config
+    }
+
+    fn open(&self, cx: &mut Context, side: Side) {
+        // 委托失败会产生 Reject 事件
+        let _ = cx.order_condition(
+            side,
+            0.0,
+            self.config.quantity,
+            self.config.margin,
+            self.config.stop_profit_condition,
+            self.config.stop_loss_condition,
+            self.config.stop_profit,
+            self.config.stop_loss,
+        );
+    }
+}
+
+impl Strategy for RuleStrategy {
+    fn on_bar(&mut self, cx: &mut Context) {
+        let test = |rule: &Option<Rule>, cx: &Context| rule.as_ref().is_some_and(|v| v.test(cx));
+
+        match cx.position().map(|v| v.side) {
+            Some(Side::BuyLong) => {
+                if test(&self.exit_long, cx) {
+                    let _ = cx.order(Side::BuySell, 0.0);
+                }
+            }
+            Some(_) => {
+                if test(&self.exit_short, cx) {
+                    let _ = cx.order(Side::SellLong, 0.0);
+                }
+            }
+            None => {
+                if test(&self.entry_long, cx) {
+                    self.open(cx, Side::BuyLong);
+                } else if test(&self.entry_short, cx) {
+                    self.open(cx, Side::SellShort);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! 仓位计算，根据权益，止损距离，波动率或者胜率计算每次开仓的数量。
+
+use crate::*;
+
+/// 仓位计算，根据当前的权益和行情计算每次开仓的数量，参考 [`Context::order_sizer`]。
+pub trait Sizer {
+    /// 计算开仓数量，单位为币，不需要处理最小委托数量和最小名义价值。
+    ///
+    /// * `cx` 上下文。
+    /// * `price` 开仓价格。
+    fn quantity(&self, cx: &Context, price: f64) -> f64;
+}
+
+/// 按照最小委托数量向下取整，名义价值小于最小名义价值时返回 0。
+///
+/// * `quantity` 数量。
+/// * `price` 价格。
+/// * `min_size` 最小委托数量。
+/// * `min_notional` 最小名义价值。
+pub fn round_quantity(quantity: f64, price: f64, min_size: f64, min_notional: f64) -> f64 {
+    if quantity.is_nan() || quantity <= 0.0 {
+        return 0.0;
+    }
+
+    let quantity = if min_size > 0.0 {
+        // 避免 0.3 / 0.1 这种精度问题
+        (quantity / min_size + 1e-9).floor() * min_size
+    } else {
+        quantity
+    };
+
+    if quantity * price < min_notional {
+        0.0
+    } else {
+        quantity
+    }
+}
+
+/// 固定比例，仓位价值为当前权益的固定比例，权益增长时仓位跟着增长。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedFractional {
+    /// 仓位价值占用权益的比例，大于 1 表示使用杠杆。
+    pub fraction: f64,
+}
+
+impl FixedFractional {
+    pub fn new(fraction: f64) -> Self {
+        Self { fraction }
+    }
+}
+
+impl Sizer for FixedFractional {
+    fn quantity(&self, cx: &Context, price: f64) -> f64 {
+        cx.equity() * self.fraction / price
+    }
+}
+
+/// 固定风险，触发止损时亏损当前权益的固定比例。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedRisk {
+    /// 每笔交易承担的风险占用权益的比例。
+    pub risk: f64,
+
+    /// 止损距离，[`Unit::Quantity`] 表示价格距离，[`Unit::Proportion`] 表示占用开仓价格的比例。
+    pub stop: Unit,
+}
+
+impl FixedRisk {
+    pub fn new(risk: f64, stop: Unit) -> Self {
+        Self { risk, stop }
+    }
+
+    /// 止损距离对应的止损触发价格，用于 [`Context::order_sizer_condition`]。
+    ///
+    /// * `cx` 上下文。
+    /// * `side` 开仓方向，[`Side::BuyLong`] 或者 [`Side::SellShort`]。
+    /// * `price` 开仓价格，0 表示使用当前的收盘价。
+    /// * `return` 止损触发价格，[`Unit::Proportion`] 保持不变，由撮合引擎按照委托价格计算。
+    pub fn stop_loss_condition(&self, cx: &Context, side: Side, price: f64) -> Unit {
+        let price = if price == 0.0 { cx.close[0] } else { price };
+
+        match self.stop {
+            Unit::Quantity(v) if side == Side::BuyLong => Unit::Quantity(price - v),
+            Unit::Quantity(v) => Unit::Quantity(price + v),
+            v => v,
+        }
+    }
+}
+
+impl Sizer for FixedRisk {
+    fn quantity(&self, cx: &Context, price: f64) -> f64 {
+        let distance = match self.stop {
+            Unit::Ignore => return 0.0,
+            Unit::Quantity(v) => v,
+            Unit::Proportion(v) => price * v,
+        };
+
+        cx.equity() * self.risk / distance
+    }
+}
+
+/// 波动率目标，使用平均真实波幅的倍数作为止损距离，波动越大仓位越小。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolatilityTarget {
+    /// 每笔交易承担的风险占用权益的比例。
+    pub risk: f64,
+
+    /// 平均真实波幅的长度。
+    pub length: usize,
+
+    /// 平均真实波幅的倍数。
+    pub mult: f64,
+}
+
+impl VolatilityTarget {
+    pub fn new(risk: f64, length: usize, mult: f64) -> Self {
+        Self { risk, length, mult }
+    }
+}
+
+impl Sizer for VolatilityTarget {
+    fn quantity(&self, cx: &Context, _price: f64) -> f64 {
+        cx.equity() * self.risk / (self.mult * atr(cx.high, cx.low, cx.close, self.length))
+    }
+}
+
+/// 凯利公式，仓位价值占用权益的比例为 `fraction * (win_rate - (1 - win_rate) / payoff)`。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kelly {
+    /// 胜率。
+    pub win_rate: f64,
+
+    /// 盈亏比，平均盈利除以平均亏损。
+    pub payoff: f64,
+
+    /// 凯利比例的系数，例如 0.5 表示半凯利。
+    pub fraction: f64,
+}
+
+impl Kelly {
+    pub fn new(win_rate: f64, payoff: f64, fraction: f64) -> Self {
+        Self {
+            win_rate,
+            payoff,
+            fraction,
+        }
+    }
+
+    /// 根据历史仓位计算胜率和盈亏比。
+    /// 没有亏损的仓位时盈亏比为无穷大，仓位比例为凯利比例的系数乘以胜率。
+    /// 没有盈利的仓位时盈亏比为 0，仓位比例为 0。
+    ///
+    /// * `history` 历史仓位。
+    /// * `fraction` 凯利比例的系数。
+    /// * `return` 历史仓位为空时返回错误。
+    pub fn from_history(history: &[Position], fraction: f64) -> anyhow::Result<Self> {
+        anyhow::ensure!(!history.is_empty(), "history is empty");
+
+        let win = history
+            .iter()
+            .filter(|v| v.profit > 0.0)
+            .collect::<Vec<_>>();
+        let loss = history
+            .iter()
+            .filter(|v| v.profit < 0.0)
+            .collect::<Vec<_>>();
+        let average =
+            |v: &[&Position]| v.iter().map(|v| v.profit.abs()).sum::<f64>() / v.len() as f64;
+
+        let payoff = if win.is_empty() {
+            0.0
+        } else if loss.is_empty() {
+            f64::INFINITY
+        } else {
+            average(&win) / average(&loss)
+        };
+
+        Ok(Self {
+            win_rate: win.len() as f64 / history.len() as f64,
+            payoff,
+            fraction,
+        })
+    }
+
+    /// 仓位价值占用权益的比例，没有优势时为 0。
+    pub fn ratio(&self) -> f64 {
+        let ratio = self.fraction * (self.win_rate - (1.0 - self.win_rate) / self.payoff);
+
+        if ratio > 0.0 {
+            ratio
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Sizer for Kelly {
+    fn quantity(&self, cx: &Context, price: f64) -> f64 {
+        cx.equity() * self.ratio() / price
+    }
+}
@@ -189,6 +189,496 @@ pub fn rsi(source: &Source, length: usize) -> f64 {
     100.0 - 100.0 / (1.0 + rs)
 }
 
+/// 总体标准差。
+///
+/// * `source` 数据系列。
+/// * `length` 长度。
+pub fn stdev(source: &Source, length: usize) -> f64 {
+    if source.len() < length {
+        return f64::NAN;
+    }
+
+    let ma = sma(source, length);
+    let sum = source
+        .iter()
+        .take(length)
+        .map(|v| (v - ma).powi(2))
+        .sum::<f64>();
+    (sum / length as f64).sqrt()
+}
+
+/// 布林带。
+///
+/// * `source` 数据系列。
+/// * `length` 长度。
+/// * `mult` 标准差的倍数。
+/// * `return` 中轨，上轨，下轨。
+pub fn bollinger(source: &Source, length: usize, mult: f64) -> (f64, f64, f64) {
+    let basis = sma(source, length);
+    let dev = mult * stdev(source, length);
+    (basis, basis + dev, basis - dev)
+}
+
+/// 加权移动平均，越新的数据权重越大。
+///
+/// * `source` 数据系列。
+/// * `length` 长度。
+pub fn wma(source: &Source, length: usize) -> f64 {
+    if source.len() < length {
+        return f64::NAN;
+    }
+
+    let sum = source
+        .iter()
+        .take(length)
+        .enumerate()
+        .map(|(i, v)| (length - i) as f64 * v)
+        .sum::<f64>();
+    sum / (length * (length + 1) / 2) as f64
+}
+
+/// 船体移动平均。
+///
+/// * `source` 数据系列。
+/// * `length` 长度。
+pub fn hma(source: &Source, length: usize) -> f64 {
+    let sqrt_length = (length as f64).sqrt() as usize;
+    let diff = (0..sqrt_length)
+        .map(|i| 2.0 * wma(&source[i..], length / 2) - wma(&source[i..], length))
+        .collect::<Vec<_>>();
+    wma(Source::new(&diff), sqrt_length)
+}
+
+/// 真实波幅，没有前一根 k 线时为最高价减最低价。
+///
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+pub fn tr(high: &Source, low: &Source, close: &Source) -> f64 {
+    if close[1].is_nan() {
+        return high - low;
+    }
+
+    (high - low)
+        .max((high - close[1]).abs())
+        .max((low - close[1]).abs())
+}
+
+/// 平均真实波幅，真实波幅的 rma。
+///
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+/// * `length` 长度。
+pub fn atr(high: &Source, low: &Source, close: &Source, length: usize) -> f64 {
+    let mut rma = RMA::new(length);
+
+    for i in (0..close.len()).rev() {
+        rma.update(tr(&high[i..], &low[i..], &close[i..]));
+    }
+
+    rma.value()
+}
+
+/// 随机指标的原始值。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `length` 长度。
+pub fn stoch(close: &Source, high: &Source, low: &Source, length: usize) -> f64 {
+    let highest = highest(high, length);
+    let lowest = lowest(low, length);
+    100.0 * (close - lowest) / (highest - lowest)
+}
+
+/// 随机指标 KD。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `length` 长度。
+/// * `k_smooth` k 的平滑长度。
+/// * `d_smooth` d 的平滑长度。
+/// * `return` k，d。
+pub fn stochastic(
+    close: &Source,
+    high: &Source,
+    low: &Source,
+    length: usize,
+    k_smooth: usize,
+    d_smooth: usize,
+) -> (f64, f64) {
+    let raw = (0..k_smooth + d_smooth - 1)
+        .map(|i| stoch(&close[i..], &high[i..], &low[i..], length))
+        .collect::<Vec<_>>();
+    let k = (0..d_smooth)
+        .map(|i| sma(Source::new(&raw[i..]), k_smooth))
+        .collect::<Vec<_>>();
+    (k[0], sma(Source::new(&k), d_smooth))
+}
+
+/// 随机相对强弱指标，rsi 的随机指标。
+///
+/// * `source` 数据系列。
+/// * `rsi_length` rsi 的长度。
+/// * `stoch_length` 随机指标的长度。
+/// * `k_smooth` k 的平滑长度。
+/// * `d_smooth` d 的平滑长度。
+/// * `return` k，d。
+pub fn stoch_rsi(
+    source: &Source,
+    rsi_length: usize,
+    stoch_length: usize,
+    k_smooth: usize,
+    d_smooth: usize,
+) -> (f64, f64) {
+    let rsi = (0..stoch_length + k_smooth + d_smooth - 2)
+        .map(|i| rsi(&source[i..], rsi_length))
+        .collect::<Vec<_>>();
+    let rsi = Source::new(&rsi);
+    stochastic(rsi, rsi, rsi, stoch_length, k_smooth, d_smooth)
+}
+
+/// 趋向指标。
+///
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `close` 收盘价数据系列。
+/// * `di_length` di 的长度。
+/// * `adx_length` adx 的长度。
+/// * `return` +di，-di，adx。
+pub fn dmi(
+    high: &Source,
+    low: &Source,
+    close: &Source,
+    di_length: usize,
+    adx_length: usize,
+) -> (f64, f64, f64) {
+    let mut plus_rma = RMA::new(di_length);
+    let mut minus_rma = RMA::new(di_length);
+    let mut tr_rma = RMA::new(di_length);
+    let mut adx_rma = RMA::new(adx_length);
+    let mut plus = f64::NAN;
+    let mut minus = f64::NAN;
+
+    // 第一根 k 线没有变化量，从第二根 k 线开始计算
+    for i in (0..close.len().saturating_sub(1)).rev() {
+        let up = high[i] - high[i + 1];
+        let down = low[i + 1] - low[i];
+        let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+        let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+        let tr = tr_rma.update(tr(&high[i..], &low[i..], &close[i..]));
+
+        plus = 100.0 * plus_rma.update(plus_dm) / tr;
+        minus = 100.0 * minus_rma.update(minus_dm) / tr;
+
+        if !plus.is_nan() && !minus.is_nan() {
+            let sum = plus + minus;
+            adx_rma.update((plus - minus).abs() / if sum == 0.0 { 1.0 } else { sum });
+        }
+    }
+
+    (plus, minus, 100.0 * adx_rma.value())
+}
+
+/// 成交量加权平均价。
+///
+/// * `source` 数据系列，一般使用 (最高价 + 最低价 + 收盘价) / 3。
+/// * `volume` 成交量数据系列。
+/// * `length` 长度。
+pub fn vwap(source: &Source, volume: &Source, length: usize) -> f64 {
+    if source.len() < length || volume.len() < length {
+        return f64::NAN;
+    }
+
+    let value = source
+        .iter()
+        .zip(volume.iter())
+        .take(length)
+        .map(|(a, b)| a * b)
+        .sum::<f64>();
+    value / volume.iter().take(length).sum::<f64>()
+}
+
+/// 能量潮，价格上涨时累加成交量，价格下跌时减去成交量。
+///
+/// * `close` 收盘价数据系列。
+/// * `volume` 成交量数据系列。
+pub fn obv(close: &Source, volume: &Source) -> f64 {
+    (0..close.len().saturating_sub(1))
+        .map(|i| {
+            let change = close[i] - close[i + 1];
+
+            if change > 0.0 {
+                volume[i]
+            } else if change < 0.0 {
+                -volume[i]
+            } else {
+                0.0
+            }
+        })
+        .sum()
+}
+
+/// 资金流量指标。
+///
+/// * `source` 数据系列，一般使用 (最高价 + 最低价 + 收盘价) / 3。
+/// * `volume` 成交量数据系列。
+/// * `length` 长度。
+pub fn mfi(source: &Source, volume: &Source, length: usize) -> f64 {
+    if source.len() < length + 1 || volume.len() < length {
+        return f64::NAN;
+    }
+
+    let mut upper = 0.0;
+    let mut lower = 0.0;
+
+    for i in 0..length {
+        let change = source[i] - source[i + 1];
+
+        if change > 0.0 {
+            upper += volume[i] * source[i];
+        } else if change < 0.0 {
+            lower += volume[i] * source[i];
+        }
+    }
+
+    100.0 - 100.0 / (1.0 + upper / lower)
+}
+
+/// 威廉指标。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `length` 长度。
+pub fn williams_r(close: &Source, high: &Source, low: &Source, length: usize) -> f64 {
+    let highest = highest(high, length);
+    let lowest = lowest(low, length);
+    100.0 * (close - highest) / (highest - lowest)
+}
+
+/// 唐奇安通道。
+///
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `length` 长度。
+/// * `return` 中轨，上轨，下轨。
+pub fn donchian(high: &Source, low: &Source, length: usize) -> (f64, f64, f64) {
+    let upper = highest(high, length);
+    let lower = lowest(low, length);
+    ((upper + lower) / 2.0, upper, lower)
+}
+
+/// 肯特纳通道，中轨为收盘价的 ema，通道宽度为真实波幅的 ema。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `length` 长度。
+/// * `mult` 真实波幅的倍数。
+/// * `return` 中轨，上轨，下轨。
+pub fn keltner(
+    close: &Source,
+    high: &Source,
+    low: &Source,
+    length: usize,
+    mult: f64,
+) -> (f64, f64, f64) {
+    let basis = ema(close, length);
+    let tr = (0..close.len())
+        .map(|i| tr(&high[i..], &low[i..], &close[i..]))
+        .collect::<Vec<_>>();
+    let range = mult * ema(Source::new(&tr), length);
+    (basis, basis + range, basis - range)
+}
+
+/// 超级趋势。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `factor` 平均真实波幅的倍数。
+/// * `atr_length` 平均真实波幅的长度。
+/// * `return` 超级趋势，方向，-1 表示上升趋势，1 表示下降趋势，和 TradingView 一致。
+pub fn supertrend(
+    close: &Source,
+    high: &Source,
+    low: &Source,
+    factor: f64,
+    atr_length: usize,
+) -> (f64, f64) {
+    let mut rma = RMA::new(atr_length);
+    let mut prev_atr = f64::NAN;
+    let mut prev_upper = f64::NAN;
+    let mut prev_lower = f64::NAN;
+    let mut prev_supertrend = f64::NAN;
+    let mut result = (f64::NAN, f64::NAN);
+
+    for i in (0..close.len()).rev() {
+        let atr = rma.update(tr(&high[i..], &low[i..], &close[i..]));
+        let src = (high[i] + low[i]) / 2.0;
+        let mut upper = src + factor * atr;
+        let mut lower = src - factor * atr;
+        let nz_upper = if prev_upper.is_nan() { 0.0 } else { prev_upper };
+        let nz_lower = if prev_lower.is_nan() { 0.0 } else { prev_lower };
+
+        if !(lower > nz_lower || close[i + 1] < nz_lower) {
+            lower = nz_lower;
+        }
+
+        if !(upper < nz_upper || close[i + 1] > nz_upper) {
+            upper = nz_upper;
+        }
+
+        let direction = if prev_atr.is_nan() {
+            1.0
+        } else if prev_supertrend == nz_upper {
+            if close[i] > upper {
+                -1.0
+            } else {
+                1.0
+            }
+        } else if close[i] < lower {
+            1.0
+        } else {
+            -1.0
+        };
+
+        let supertrend = if direction == -1.0 { lower } else { upper };
+
+        result = if atr.is_nan() {
+            (f64::NAN, f64::NAN)
+        } else {
+            (supertrend, direction)
+        };
+
+        prev_atr = atr;
+        prev_upper = upper;
+        prev_lower = lower;
+        prev_supertrend = supertrend;
+    }
+
+    result
+}
+
+/// 抛物线转向指标。
+///
+/// * `close` 收盘价数据系列。
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `start` 加速因子的初始值。
+/// * `increment` 加速因子的增量。
+/// * `maximum` 加速因子的最大值。
+pub fn sar(
+    close: &Source,
+    high: &Source,
+    low: &Source,
+    start: f64,
+    increment: f64,
+    maximum: f64,
+) -> f64 {
+    let len = close.len();
+
+    if len < 2 {
+        return f64::NAN;
+    }
+
+    let mut result = f64::NAN;
+    let mut extreme = f64::NAN;
+    let mut acceleration = start;
+    let mut below = false;
+
+    // 从第二根 k 线开始计算
+    for i in (0..len - 1).rev() {
+        let mut first = false;
+
+        if i == len - 2 {
+            below = close[i] > close[i + 1];
+
+            if below {
+                extreme = high[i];
+                result = low[i + 1];
+            } else {
+                extreme = low[i];
+                result = high[i + 1];
+            }
+
+            first = true;
+            acceleration = start;
+        }
+
+        result += acceleration * (extreme - result);
+
+        if below {
+            if result > low[i] {
+                first = true;
+                below = false;
+                result = high[i].max(extreme);
+                extreme = low[i];
+                acceleration = start;
+            }
+        } else if result < high[i] {
+            first = true;
+            below = true;
+            result = low[i].min(extreme);
+            extreme = high[i];
+            acceleration = start;
+        }
+
+        if !first {
+            if below {
+                if high[i] > extreme {
+                    extreme = high[i];
+                    acceleration = (acceleration + increment).min(maximum);
+                }
+            } else if low[i] < extreme {
+                extreme = low[i];
+                acceleration = (acceleration + increment).min(maximum);
+            }
+        }
+
+        if below {
+            result = result.min(low[i + 1]);
+
+            if i + 2 < len {
+                result = result.min(low[i + 2]);
+            }
+        } else {
+            result = result.max(high[i + 1]);
+
+            if i + 2 < len {
+                result = result.max(high[i + 2]);
+            }
+        }
+    }
+
+    result
+}
+
+/// 一目均衡表。
+/// 先行带向前平移，当前的云带是 `displacement - 1` 根 k 线之前计算的先行带，即 `ichimoku(&high[displacement - 1..], &low[displacement - 1..], ..)`。
+///
+/// * `high` 最高价数据系列。
+/// * `low` 最低价数据系列。
+/// * `conversion_length` 转换线的长度。
+/// * `base_length` 基准线的长度。
+/// * `span_b_length` 先行带 b 的长度。
+/// * `return` 转换线，基准线，先行带 a，先行带 b。
+pub fn ichimoku(
+    high: &Source,
+    low: &Source,
+    conversion_length: usize,
+    base_length: usize,
+    span_b_length: usize,
+) -> (f64, f64, f64, f64) {
+    let conversion = donchian(high, low, conversion_length).0;
+    let base = donchian(high, low, base_length).0;
+    let span_b = donchian(high, low, span_b_length).0;
+    (conversion, base, (conversion + base) / 2.0, span_b)
+}
+
 /// 如果在当前 k 线上，`source` 的值大于 `value` 的值，并且在前一根 k 线上，`source` 的值小于或等于 `value` 的值，则返回 true。
 ///
 /// * `source` 数据系列。
@@ -671,7 +1161,187 @@ where
     result
 }
 
-/// 快速计算 ema。
+/// 将逐笔成交聚合成 k 线。
+///
+/// * `array` 逐笔成交数组，新的数据在前面。
+/// * `level` 时间级别。
+/// * `return` k 线数组，新的数据在前面。
+pub fn trade_to_k<T>(array: T, level: Level) -> Vec<K>
+where
+    T: AsRef<[Trade]>,
+{
+    let mut result: Vec<K> = Vec::new();
+
+    for trade in array.as_ref().iter().rev() {
+        // k_time_convert 不会对齐 1 分钟级别的时间戳，逐笔成交的时间需要先对齐到分钟
+        let time = match level {
+            Level::Minute1 => trade.time / (1000 * 60) * (1000 * 60),
+            _ => k_time_convert(trade.time, level).0,
+        };
+
+        match result.last_mut() {
+            Some(k) if k.time == time => {
+                k.high = k.high.max(trade.price);
+                k.low = k.low.min(trade.price);
+                k.close = trade.price;
+            }
+            _ => result.push(K {
+                time,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+            }),
+        }
+    }
+
+    result.reverse();
+
+    result
+}
+
+/// 逐笔成交的 csv 格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeFormat {
+    /// 币安的 trades 数据，`id,price,qty,quote_qty,time,is_buyer_maker[,is_best_match]`。
+    Binance,
+
+    /// 币安的 aggTrades 数据，`agg_trade_id,price,quantity,first_trade_id,last_trade_id,transact_time,is_buyer_maker[,is_best_match]`。
+    BinanceAgg,
+
+    /// 欧易的 trades 数据，必须包含表头，根据表头的 side，price，size，created_time 读取数据。
+    Okx,
+}
+
+/// 读取交易所的逐笔成交 csv 文件。
+/// 表头可有可无，时间戳为微秒时会转换成毫秒。
+///
+/// * `path` 文件路径。
+/// * `format` csv 格式。
+/// * `return` 逐笔成交数组，新的数据在前面。
+pub fn read_trade_csv<P>(path: P, format: TradeFormat) -> anyhow::Result<Vec<Trade>>
+where
+    P: AsRef<std::path::Path>,
+{
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("read {}: {}", path.display(), e))?;
+    parse_trade_csv(text, format)
+}
+
+/// 解析交易所的逐笔成交 csv 数据。
+/// 表头可有可无，时间戳为微秒时会转换成毫秒。
+///
+/// * `text` csv 数据。
+/// * `format` csv 格式。
+/// * `return` 逐笔成交数组，新的数据在前面。
+pub fn parse_trade_csv<S>(text: S, format: TradeFormat) -> anyhow::Result<Vec<Trade>>
+where
+    S: AsRef<str>,
+{
+    let mut lines = text
+        .as_ref()
+        .lines()
+        .map(|v| v.trim())
+        .enumerate()
+        .filter(|(_, v)| !v.is_empty())
+        .peekable();
+
+    // 第一个字段不是数字则认为是表头
+    let header = match lines.peek() {
+        Some((_, v)) if v.split(',').next().unwrap().parse::<f64>().is_err() => lines
+            .next()
+            .unwrap()
+            .1
+            .split(',')
+            .map(|v| v.trim().to_lowercase())
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+
+    let column = |name: &[&str]| {
+        header
+            .iter()
+            .position(|v| name.contains(&v.as_str()))
+            .ok_or(anyhow::anyhow!("no column: {}", name[0]))
+    };
+
+    // 价格，数量，时间，方向
+    let (price, size, time, side) = match format {
+        TradeFormat::Binance => (1, 2, 4, 5),
+        TradeFormat::BinanceAgg => (1, 2, 5, 6),
+        TradeFormat::Okx => {
+            anyhow::ensure!(!header.is_empty(), "okx trade csv must have a header");
+            (
+                column(&["price", "px"])?,
+                column(&["size", "sz", "qty"])?,
+                column(&["created_time", "ts", "timestamp", "time"])?,
+                column(&["side"])?,
+            )
+        }
+    };
+
+    let mut result = Vec::new();
+
+    // 错误信息中的行号为文件中的行号
+    for (i, line) in lines {
+        let field = line.split(',').map(|v| v.trim()).collect::<Vec<_>>();
+
+        let get = |index: usize| {
+            field.get(index).copied().ok_or(anyhow::anyhow!(
+                "line {}: missing column {}",
+                i + 1,
+                index
+            ))
+        };
+
+        let parse = |index: usize| -> anyhow::Result<f64> {
+            let value = get(index)?;
+            value
+                .parse::<f64>()
+                .map_err(|e| anyhow::anyhow!("line {}: {}: {}", i + 1, value, e))
+        };
+
+        let mut timestamp = parse(time)? as u64;
+
+        // 微秒
+        if timestamp >= 10_000_000_000_000 {
+            timestamp /= 1000;
+        }
+
+        let side = match format {
+            TradeFormat::Binance | TradeFormat::BinanceAgg => {
+                // 买方是挂单方，则主动成交方向是卖出
+                match get(side)?.to_lowercase().as_str() {
+                    "true" => TradeSide::Sell,
+                    "false" => TradeSide::Buy,
+                    v => anyhow::bail!("line {}: unknown is_buyer_maker: {}", i + 1, v),
+                }
+            }
+            TradeFormat::Okx => match get(side)?.to_lowercase().as_str() {
+                "buy" => TradeSide::Buy,
+                "sell" => TradeSide::Sell,
+                v => anyhow::bail!("line {}: unknown side: {}", i + 1, v),
+            },
+        };
+
+        result.push(Trade {
+            time: timestamp,
+            price: parse(price)?,
+            size: parse(size)?,
+            side,
+        });
+    }
+
+    // 交易所的数据从旧到新排列
+    if result.first().map(|v| v.time) < result.last().map(|v| v.time) {
+        result.reverse();
+    }
+
+    Ok(result)
+}
+
+/// 快速计算 ema，参考增量计算的 [`EMA`]。
 pub struct EMACache {
     last: f64,
 }
@@ -701,7 +1371,7 @@ impl EMACache {
     }
 }
 
-/// 快速计算 rma。
+/// 快速计算 rma，参考增量计算的 [`RMA`]。
 pub struct RMACache {
     last: f64,
 }
@@ -731,7 +1401,7 @@ impl RMACache {
     }
 }
 
-/// 快速计算 macd。
+/// 快速计算 macd，参考增量计算的 [`MACD`]。
 pub struct MACDCache {
     short_ema: EMACache,
     long_ema: EMACache,
@@ -777,7 +1447,7 @@ impl MACDCache {
     }
 }
 
-/// 快速计算 rsi。
+/// 快速计算 rsi，参考增量计算的 [`RSI`]。
 pub struct RSICache {
     u: std::collections::VecDeque<f64>,
     d: std::collections::VecDeque<f64>,
@@ -824,6 +1494,38 @@ impl RSICache {
     }
 }
 
+/// 获取产品类型。
+/// 按照欧易的产品 id 格式识别，永续合约以 SWAP 结尾，交割合约的第三段为 6 位的交割日期，期权以 C 或者 P 结尾。
+///
+/// * `product` 交易产品，例如，现货 BTC-USDT，永续合约 BTC-USDT-SWAP，交割合约 BTC-USD-231229。
+pub fn instrument<S>(product: S) -> Instrument
+where
+    S: AsRef<str>,
+{
+    let product = product.as_ref();
+    let part = product.split('-').collect::<Vec<_>>();
+    let date = |v: &str| v.len() == 6 && v.bytes().all(|v| v.is_ascii_digit());
+
+    match part[..] {
+        // 没有分隔符的产品，参考 product_mapping
+        [v] if v.ends_with("SWAP") => Instrument::Swap,
+        [.., "SWAP"] => Instrument::Swap,
+        [_, _, v, _, "C" | "P"] if date(v) => Instrument::Option,
+        [_, _, v] if date(v) => Instrument::Futures,
+        _ => Instrument::Spot,
+    }
+}
+
+/// 是否为现货产品。
+///
+/// * `product` 交易产品，例如，现货 BTC-USDT，合约 BTC-USDT-SWAP。
+pub fn is_spot<S>(product: S) -> bool
+where
+    S: AsRef<str>,
+{
+    instrument(product) == Instrument::Spot
+}
+
 /// 交易产品映射。
 /// BTC-USDT <-> BTCUSDT。
 /// BTC-USDT-SWAP <-> BTCUSDTSWAP。
@@ -1356,7 +2058,7 @@ where
     }
 }
 
-/// 回测结果转换到 html 文本。
+/// 回测结果转换到 html 文本，更完整的回测报告参考 [`Report`]。
 ///
 /// * `k` k 线数据。
 /// * `result` 回测结果。
@@ -1407,4 +2109,5 @@ where
         }
     }
     text.replace("{mark}", &mark)
+        .replace("{klinecharts}", include_str!("../klinecharts.js"))
 }
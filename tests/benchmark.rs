@@ -0,0 +1,155 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn test_benchmark1() {
+    let data = k(&[10.0, 11.0, 12.0, 13.0, 14.0]);
+
+    // 第二根 k 线开始，每两根 k 线一个数据
+    let benchmark = vec![
+        K {
+            time: 240_000,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            close: 60.0,
+        },
+        K {
+            time: 120_000,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            close: 50.0,
+        },
+    ];
+    assert_eq!(
+        benchmark_curve(&data, &benchmark, 100.0),
+        [120.0, 120.0, 100.0, 100.0, 100.0]
+    );
+    assert_eq!(
+        benchmark_curve(&data, &data, 100.0),
+        [140.0, 130.0, 120.0, 110.0, 100.0]
+    );
+    assert!(benchmark_curve([], &data, 100.0).is_empty());
+}
+
+#[test]
+fn test_benchmark2() {
+    // 旧的数据在后面
+    let benchmark = [108.9, 99.0, 110.0, 100.0];
+    let comparison = Comparison::new(benchmark, benchmark);
+    assert!(approx(comparison.beta, 1.0));
+    assert!(approx(comparison.correlation, 1.0));
+    assert!(approx(comparison.alpha, 0.0));
+    assert!(approx(comparison.excess_return, 0.0));
+    assert_eq!(comparison.tracking_error, 0.0);
+    assert_eq!(comparison.information_ratio, 0.0);
+    assert_eq!(comparison.benchmark, benchmark);
+
+    // 两倍的收益率
+    let equity = [115.2, 96.0, 120.0, 100.0];
+    let comparison = Comparison::new(equity, benchmark);
+    assert!(approx(comparison.strategy_return, 0.152));
+    assert!(approx(comparison.benchmark_return, 0.089));
+    assert!(approx(comparison.excess_return, 0.063));
+    assert!(approx(comparison.beta, 2.0));
+    assert!(approx(comparison.correlation, 1.0));
+    assert!(approx(comparison.alpha, 0.0));
+
+    // 超额收益率为 0.1，-0.1，0.1
+    let mean = 0.1_f64 / 3.0;
+    let tracking_error = ((2.0 * (0.1 - mean).powi(2) + (-0.1 - mean).powi(2)) / 3.0).sqrt();
+    assert!(approx(comparison.tracking_error, tracking_error));
+    assert!(approx(comparison.information_ratio, mean / tracking_error));
+
+    assert_eq!(Comparison::new([], []), Comparison::default());
+}
+
+#[tokio::test]
+async fn test_benchmark3() {
+    let close = [100.0, 102.0, 101.0, 105.0, 104.0, 108.0];
+    let other = [50.0, 49.0, 48.0, 50.0, 51.0, 52.0];
+    let backtester = Backtester::new(
+        exchange(&close).push("ETH-USDT-SWAP", Level::Minute1, k(&other), 0.01, 0.0),
+        config().quantity(Unit::Quantity(1.0)),
+    );
+
+    let history = backtester
+        .start(
+            |cx| {
+                if cx.close.len() == 1 {
+                    cx.order(Side::BuyLong, 0.0).unwrap();
+                }
+
+                if cx.close.len() == 6 {
+                    cx.order(Side::BuySell, 0.0).unwrap();
+                }
+            },
+            "BTC-USDT-SWAP",
+            Level::Minute1,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let comparison = backtester
+        .compare(
+            "BTC-USDT-SWAP",
+            Level::Minute1,
+            0,
+            &history,
+            Benchmark::BuyAndHold,
+        )
+        .await
+        .unwrap();
+    assert!(approx(comparison.benchmark_return, 0.08));
+    assert!(approx(comparison.strategy_return, 8.0 / 1000.0));
+    assert!(approx(comparison.excess_return, 0.008 - 0.08));
+    assert!(comparison.correlation > 0.99);
+    assert!(comparison.beta > 0.0 && comparison.beta < 1.0);
+    assert_eq!(comparison.benchmark.len(), close.len());
+
+    let comparison = backtester
+        .compare(
+            "BTC-USDT-SWAP",
+            Level::Minute1,
+            0,
+            &history,
+            Benchmark::Product("ETH-USDT-SWAP".to_string()),
+        )
+        .await
+        .unwrap();
+    assert!(approx(comparison.benchmark_return, 0.04));
+
+    // 自定义的基准只使用时间范围之内的数据
+    let comparison = backtester
+        .compare(
+            "BTC-USDT-SWAP",
+            Level::Minute1,
+            120_000..,
+            &history,
+            Benchmark::Custom(k(&other)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(comparison.benchmark.len(), close.len() - 1);
+    assert!(approx(comparison.benchmark_return, 52.0 / 49.0 - 1.0));
+
+    let backtester = Backtester::new(exchange(&close), Config::new());
+    assert!(backtester
+        .compare(
+            "BTC-USDT-SWAP",
+            Level::Minute1,
+            0,
+            &history,
+            Benchmark::BuyAndHold
+        )
+        .await
+        .is_err());
+}
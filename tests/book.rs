@@ -0,0 +1,147 @@
+use auto_trading::*;
+
+fn book(bids: Vec<(f64, f64)>, asks: Vec<(f64, f64)>) -> BookUpdate {
+    BookUpdate {
+        time: 1,
+        snapshot: true,
+        bids,
+        asks,
+    }
+}
+
+fn trade(time: u64, price: f64, size: f64, side: TradeSide) -> Trade {
+    Trade {
+        time,
+        price,
+        size,
+        side,
+    }
+}
+
+fn order(me: &mut MatchEngine, side: Side, price: f64, quantity: f64) -> u64 {
+    me.order(
+        "BTC-USDT-SWAP",
+        side,
+        price,
+        Unit::Quantity(quantity),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap()
+}
+
+fn new_match_engine() -> MatchEngine {
+    let config = Config::new().initial_margin(100000.0).lever(1);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me
+}
+
+#[test]
+fn test_order_book() {
+    let mut ob = OrderBook::new();
+    ob.apply(&book(
+        vec![(19990.0, 1.0), (20000.0, 2.0)],
+        vec![(20020.0, 1.0), (20010.0, 0.5)],
+    ));
+    assert!(ob.bids == vec![(20000.0, 2.0), (19990.0, 1.0)], "{:#?}", ob);
+    assert!(ob.asks == vec![(20010.0, 0.5), (20020.0, 1.0)], "{:#?}", ob);
+    ob.apply(&BookUpdate {
+        time: 2,
+        snapshot: false,
+        bids: vec![(20000.0, 0.0), (19995.0, 3.0)],
+        asks: vec![(20010.0, 0.2)],
+    });
+    assert!(ob.bids == vec![(19995.0, 3.0), (19990.0, 1.0)], "{:#?}", ob);
+    assert!(ob.best_ask() == Some(20010.0), "{:#?}", ob);
+    assert!(ob.size(false, 20010.0) == 0.2, "{:#?}", ob);
+    // 0.2 * 20010 + 0.8 * 20020
+    assert!(
+        (ob.walk(true, 1.0).unwrap() - 20018.0).abs() < 1e-9,
+        "{:#?}",
+        ob
+    );
+    // 深度不足按最后一档成交
+    assert!(
+        (ob.walk(false, 5.0).unwrap() - 19993.0).abs() < 1e-9,
+        "{:#?}",
+        ob
+    );
+}
+
+#[test]
+fn test_book_queue() {
+    // 排在前面的数量被成交完才会成交
+    let mut me = new_match_engine();
+    me.ready_book(
+        "BTC-USDT-SWAP",
+        &book(vec![(19990.0, 2.0)], vec![(20000.0, 1.0)]),
+    );
+    me.ready_trade("BTC-USDT-SWAP", &trade(1, 20000.0, 0.1, TradeSide::Buy));
+    order(&mut me, Side::BuyLong, 19990.0, 1.0);
+    me.update();
+    me.ready_trade("BTC-USDT-SWAP", &trade(2, 19990.0, 1.5, TradeSide::Sell));
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    // 撤单发生在前面，排队数量变成 min(0.5, 1.0)
+    me.ready_book(
+        "BTC-USDT-SWAP",
+        &BookUpdate {
+            time: 3,
+            snapshot: false,
+            bids: vec![(19990.0, 1.0)],
+            asks: Vec::new(),
+        },
+    );
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    me.ready_trade("BTC-USDT-SWAP", &trade(4, 19990.0, 0.6, TradeSide::Sell));
+    me.update();
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert!(position.open_price == 19990.0, "{:#?}", me);
+    assert!(position.open_time == 4, "{:#?}", me);
+}
+
+#[test]
+fn test_book_through() {
+    // 成交价格穿过委托价格
+    let mut me = new_match_engine();
+    me.ready_book(
+        "BTC-USDT-SWAP",
+        &book(vec![(19990.0, 2.0)], vec![(20000.0, 1.0)]),
+    );
+    me.ready_trade("BTC-USDT-SWAP", &trade(1, 20000.0, 0.1, TradeSide::Buy));
+    order(&mut me, Side::BuyLong, 19980.0, 1.0);
+    me.update();
+    me.ready_trade("BTC-USDT-SWAP", &trade(2, 19980.0, 5.0, TradeSide::Buy));
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    me.ready_trade("BTC-USDT-SWAP", &trade(3, 19970.0, 0.01, TradeSide::Sell));
+    me.update();
+    assert!(
+        me.position("BTC-USDT-SWAP").unwrap().open_price == 19980.0,
+        "{:#?}",
+        me
+    );
+}
+
+#[test]
+fn test_book_walk() {
+    // 市价委托吃掉多个价格档位
+    let mut me = new_match_engine();
+    me.ready_book(
+        "BTC-USDT-SWAP",
+        &book(vec![(19990.0, 2.0)], vec![(20000.0, 0.5), (20010.0, 1.0)]),
+    );
+    order(&mut me, Side::BuyLong, 0.0, 1.0);
+    me.update();
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert!(position.open_price == 20005.0, "{:#?}", me);
+    // 市价平仓吃买盘
+    order(&mut me, Side::BuySell, 0.0, 1.0);
+    me.update();
+    assert!(me.history()[0].close_price == 19990.0, "{:#?}", me);
+}
@@ -0,0 +1,42 @@
+//! 集成测试共用的 k 线数据和回测设置，每个测试文件只使用其中的一部分。
+
+#![allow(dead_code)]
+
+use auto_trading::*;
+
+/// 新的数据在前面，时间从 1 分钟开始，最高价和最低价为收盘价加减 1。
+pub fn k(close: &[f64]) -> Vec<K> {
+    close
+        .iter()
+        .enumerate()
+        .map(|(i, v)| K {
+            time: (i as u64 + 1) * 60_000,
+            open: *v,
+            high: *v + 1.0,
+            low: *v - 1.0,
+            close: *v,
+        })
+        .rev()
+        .collect()
+}
+
+/// 只有 1 分钟级别的 BTC-USDT-SWAP 的本地交易所。
+pub fn exchange(close: &[f64]) -> LocalExchange {
+    LocalExchange::new().push("BTC-USDT-SWAP", Level::Minute1, k(close), 0.01, 0.0)
+}
+
+/// 初始保证金 1000，杠杆 10。
+pub fn config() -> Config {
+    Config::new().initial_margin(1000.0).lever(10)
+}
+
+/// 使用 1 分钟级别的 BTC-USDT-SWAP 回测。
+pub async fn backtest<F>(close: &[f64], config: Config, strategy: F) -> (Vec<Position>, Vec<Event>)
+where
+    F: Strategy,
+{
+    Backtester::new(exchange(close), config)
+        .start_strategy(strategy, "BTC-USDT-SWAP", Level::Minute1, Level::Minute1, 0)
+        .await
+        .unwrap()
+}
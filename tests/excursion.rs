@@ -0,0 +1,131 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+/// 第 1 根 k 线开仓，第 6 根 k 线平仓。
+async fn open_close(side: Side, path: PricePath) -> Vec<Position> {
+    let config = config().quantity(Unit::Quantity(2.0)).path(path);
+
+    let (history, _) = backtest(
+        &[100.0, 99.0, 101.0, 105.0, 104.0, 103.0],
+        config,
+        |cx: &mut Context| {
+            if cx.close.len() == 1 {
+                cx.order(side, 0.0).unwrap();
+            }
+
+            if cx.close.len() == 6 {
+                cx.order(
+                    if side == Side::BuyLong {
+                        Side::BuySell
+                    } else {
+                        Side::SellLong
+                    },
+                    0.0,
+                )
+                .unwrap();
+            }
+        },
+    )
+    .await;
+
+    history
+}
+
+#[tokio::test]
+async fn test_excursion1() {
+    let history = open_close(Side::BuyLong, PricePath::Whole).await;
+    let excursion = history[0].excursion;
+    assert_eq!(excursion.adverse_price, 98.0);
+    assert_eq!(excursion.adverse_profit, -4.0);
+    assert_eq!(excursion.adverse_time, 60_000);
+    assert_eq!(excursion.favorable_price, 106.0);
+    assert_eq!(excursion.favorable_profit, 12.0);
+    assert_eq!(excursion.favorable_time, 180_000);
+    assert_eq!(excursion.bars, 5);
+
+    let history = open_close(Side::SellShort, PricePath::Whole).await;
+    let excursion = history[0].excursion;
+    assert_eq!(excursion.adverse_price, 106.0);
+    assert_eq!(excursion.adverse_profit, -12.0);
+    assert_eq!(excursion.adverse_time, 180_000);
+    assert_eq!(excursion.favorable_price, 98.0);
+    assert_eq!(excursion.favorable_profit, 4.0);
+    assert_eq!(excursion.favorable_time, 60_000);
+    assert_eq!(excursion.bars, 5);
+}
+
+#[tokio::test]
+async fn test_excursion2() {
+    let history = open_close(Side::BuyLong, PricePath::Whole).await;
+    let point = excursion_points(&history);
+    assert_eq!(point.len(), 1);
+    assert_eq!(point[0].side, Side::BuyLong);
+    assert_eq!(point[0].adverse, -4.0);
+    assert!((point[0].adverse_ratio + 0.02).abs() < 1e-12);
+    assert_eq!(point[0].favorable, 12.0);
+    assert!((point[0].favorable_ratio - 0.06).abs() < 1e-12);
+    assert_eq!(point[0].profit, history[0].profit - history[0].fee);
+    assert_eq!(point[0].bars, 5);
+    assert_eq!(point[0].favorable_time, 180_000);
+
+    // 旧的历史仓位没有偏移
+    let mut value = serde_json::to_value(&history[0]).unwrap();
+    value.as_object_mut().unwrap().remove("excursion");
+    let position = serde_json::from_value::<Position>(value).unwrap();
+    assert_eq!(position.excursion, Excursion::default());
+}
+
+#[tokio::test]
+async fn test_excursion3() {
+    // k 线拆分成多段价格变动时，每根 k 线只统计一次
+    let expected = open_close(Side::BuyLong, PricePath::Whole).await[0].excursion;
+
+    for path in [PricePath::OpenHighLowClose, PricePath::OpenLowHighClose] {
+        let history = open_close(Side::BuyLong, path).await;
+        assert_eq!(history[0].excursion.bars, 5, "{:?}", path);
+        assert_eq!(history[0].excursion, expected, "{:?}", path);
+    }
+}
+
+#[test]
+fn test_excursion4() {
+    // 逐笔成交撮合时按照 1 分钟的 k 线统计
+    let trade = |time: u64, price: f64| Trade {
+        time,
+        price,
+        size: 1.0,
+        side: TradeSide::Buy,
+    };
+    let mut me = MatchEngine::new(config());
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready_trade("BTC-USDT-SWAP", &trade(1, 100.0));
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Quantity(1.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+
+    for (time, price) in [(2, 100.0), (3, 101.0), (59_999, 99.0)] {
+        me.ready_trade("BTC-USDT-SWAP", &trade(time, price));
+        me.update();
+    }
+
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.excursion.bars, 1);
+    assert_eq!(position.excursion.favorable_price, 101.0);
+
+    me.ready_trade("BTC-USDT-SWAP", &trade(60_000, 100.0));
+    me.update();
+    me.ready_trade("BTC-USDT-SWAP", &trade(60_001, 100.0));
+    me.update();
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().excursion.bars, 2);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+#[test]
+fn test_export1() {
+    let position = serde_json::from_str::<Position>(
+        r#"{"product":"A,\"B\"","lever":2,"side":"SellShort","open_price":10.0,"quantity":1.0,"margin":5.0,"liquidation_price":14.5,"close_price":8.0,"profit":2.0,"profit_ratio":0.4,"fee":0.5,"open_time":60000,"close_time":120000,"log":[
+            {"side":"SellShort","price":10.0,"quantity":1.0,"margin":5.0,"fee":0.25,"profit":0.0,"profit_ratio":0.0,"time":60000,"maker":false},
+            {"side":"SellLong","price":8.0,"quantity":1.0,"margin":5.0,"fee":0.25,"profit":2.0,"profit_ratio":0.4,"time":120000,"maker":true}
+        ]}"#,
+    )
+    .unwrap();
+
+    let mut buffer = Vec::new();
+    write_trades_csv(&mut buffer, [position.clone()]).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "id,product,lever,side,open_time,close_time,open_price,close_price,quantity,margin,liquidation_price,profit,fee,net_profit,profit_ratio,fills\n\
+         0,\"A,\"\"B\"\"\",2,SellShort,60000,120000,10,8,1,5,14.5,2,0.5,1.5,0.4,2\n"
+    );
+
+    let mut buffer = Vec::new();
+    write_fills_csv(&mut buffer, [position.clone()]).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "position,product,side,time,price,quantity,margin,fee,profit,profit_ratio,maker\n\
+         0,\"A,\"\"B\"\"\",SellShort,60000,10,1,5,0.25,0,0,false\n\
+         0,\"A,\"\"B\"\"\",SellLong,120000,8,1,5,0.25,2,0.4,true\n"
+    );
+
+    let equity = equity_points(k(&[10.0, 9.0, 8.0]), [position], 100.0);
+    assert_eq!(equity.len(), 3);
+    assert_eq!(equity[0].time, 60000);
+    assert_eq!(equity[0].equity, 99.75);
+    assert_eq!(equity[1].equity, 101.5);
+    assert_eq!(equity[2].equity, 101.5);
+
+    let mut buffer = Vec::new();
+    write_equity_csv(&mut buffer, &equity).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert_eq!(text.lines().count(), 4);
+    assert!(text.starts_with("time,close,equity,drawdown\n60000,10,99.75,0\n"));
+}
+
+#[tokio::test]
+async fn test_export2() {
+    let close = [100.0, 101.0, 103.0, 102.0, 99.0, 104.0];
+    let data = k(&close);
+    let config = config().quantity(Unit::Quantity(1.0));
+
+    let (history, _) = backtest(&close, config, |cx: &mut Context| match cx.close.len() {
+        1 | 4 => {
+            cx.order(Side::BuyLong, 0.0).unwrap();
+        }
+        3 | 6 => {
+            cx.order(Side::BuySell, 0.0).unwrap();
+        }
+        _ => {}
+    })
+    .await;
+    assert_eq!(history.len(), 2);
+
+    let metadata = Metadata::new("BTC-USDT-SWAP", Level::Minute1, Level::Minute1, ..);
+    assert_eq!(metadata.range.start, 0);
+    assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+
+    let bundle = Bundle::new(metadata.clone(), config, &data, history.clone());
+    assert_eq!(bundle.summary.trade, 2);
+    assert_eq!(bundle.equity.len(), close.len());
+    assert_eq!(
+        bundle.equity.last().unwrap().equity,
+        bundle.summary.final_equity
+    );
+
+    let bundle = Bundle::from_json(bundle.to_json().unwrap()).unwrap();
+    assert_eq!(bundle.metadata, metadata);
+    assert_eq!(bundle.history.len(), 2);
+    assert_eq!(bundle.history[1].open_price, history[1].open_price);
+
+    let dir = std::env::temp_dir().join(format!("auto-trading-export-{}", std::process::id()));
+    bundle.save(dir.join("result.json")).unwrap_err();
+    bundle.save_csv(&dir).unwrap();
+    bundle.save(dir.join("result.json")).unwrap();
+    assert_eq!(
+        Bundle::load(dir.join("result.json")).unwrap().summary,
+        bundle.summary
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("trades.csv"))
+            .unwrap()
+            .lines()
+            .count(),
+        3
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("fills.csv"))
+            .unwrap()
+            .lines()
+            .count(),
+        5
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("equity.csv"))
+            .unwrap()
+            .lines()
+            .count(),
+        close.len() + 1
+    );
+    assert!(Bundle::load(dir.join("missing.json")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
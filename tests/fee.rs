@@ -0,0 +1,336 @@
+use auto_trading::*;
+
+#[test]
+fn test_fee_schedule() {
+    let fee = FeeSchedule::new(0.0002, 0.0005)
+        .tier(1_000_000.0, 0.0, 0.0004)
+        .tier(100_000.0, 0.0001, 0.00045)
+        .product("ETH-USDT-SWAP", -0.0001, 0.0003)
+        .discount(0.2);
+    assert_eq!(
+        fee.tier.iter().map(|v| v.min_volume).collect::<Vec<_>>(),
+        vec![0.0, 100_000.0, 1_000_000.0]
+    );
+    let rate = |product, maker, volume| fee.rate(product, Side::BuyLong, maker, volume);
+    assert_eq!(rate("BTC-USDT-SWAP", true, 0.0), 0.0002 * 0.8);
+    assert_eq!(rate("BTC-USDT-SWAP", false, 0.0), 0.0005 * 0.8);
+    assert_eq!(rate("BTC-USDT-SWAP", true, 100_000.0), 0.0001 * 0.8);
+    assert_eq!(rate("BTC-USDT-SWAP", true, 2_000_000.0), 0.0);
+    assert_eq!(rate("BTC-USDT-SWAP", false, 2_000_000.0), 0.0004 * 0.8);
+
+    // 返佣不打折
+    assert_eq!(rate("ETH-USDT-SWAP", true, 2_000_000.0), -0.0001);
+    assert_eq!(rate("ETH-USDT-SWAP", false, 0.0), 0.0003 * 0.8);
+}
+
+#[test]
+fn test_fee1() {
+    // 吃单开仓，挂单平仓返佣
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_fee(FeeSchedule::new(-0.0001, 0.0005)).unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert!(me.set_fee(FeeSchedule::new(0.0, 0.0)).is_err());
+    // 按照吃单预先扣除估计的手续费
+    assert_eq!(me.balance(), 979.9);
+    me.update();
+    let record = me.position("BTC-USDT-SWAP").unwrap().log[0];
+    assert!(!record.maker);
+    assert_eq!(record.fee, 0.1);
+    assert_eq!(me.balance(), 979.9);
+    assert_eq!(me.volume(), 200.0);
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuySell,
+        21000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21000.0,
+            low: 20000.0,
+            close: 21000.0,
+        },
+    );
+    me.update();
+    let position = &me.history()[0];
+    let record = position.log[1];
+    assert!(record.maker);
+    assert!((record.fee + 0.021).abs() < 1e-9);
+    assert!((position.fee - 0.079).abs() < 1e-9);
+    assert!((me.balance() - 1009.921).abs() < 1e-9);
+    assert_eq!(me.volume(), 410.0);
+}
+
+#[test]
+fn test_fee2() {
+    // 过去 30 天的成交额达到等级后使用新的费率，超过 30 天的成交额不再统计
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_fee(FeeSchedule::new(0.0002, 0.0005).tier(300.0, 0.0, 0.0))
+        .unwrap();
+    let day = 24 * 60 * 60 * 1000;
+    for (time, side) in [
+        (day, Side::BuyLong),
+        (day * 2, Side::BuySell),
+        (day * 3, Side::BuyLong),
+        (day * 40, Side::BuySell),
+    ] {
+        me.ready(
+            "BTC-USDT-SWAP",
+            K {
+                time,
+                open: 20000.0,
+                high: 20000.0,
+                low: 20000.0,
+                close: 20000.0,
+            },
+        );
+        me.order(
+            "BTC-USDT-SWAP",
+            side,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+        me.update();
+    }
+    let fee = me
+        .history()
+        .iter()
+        .flat_map(|v| v.log.iter().map(|v| v.fee))
+        .collect::<Vec<_>>();
+    assert_eq!(fee, vec![0.1, 0.1, 0.0, 0.1]);
+    assert_eq!(me.volume(), 200.0);
+}
+
+#[test]
+fn test_fee3() {
+    // 限价委托的价格等于收盘价时仍然是挂单
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_fee(FeeSchedule::new(0.0002, 0.0005)).unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    for side in [Side::BuyLong, Side::BuySell] {
+        me.order(
+            "BTC-USDT-SWAP",
+            side,
+            20000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+        me.update();
+    }
+    let log = &me.history()[0].log;
+    assert!(log.iter().all(|v| v.maker), "{:#?}", log);
+    assert_eq!(log[0].fee, 0.04);
+}
+
+#[test]
+fn test_fee4() {
+    // 挂单成交时退还预先扣除的吃单手续费的差额，撤销时全部退还
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_fee(FeeSchedule::new(0.0002, 0.0005)).unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    let order = |me: &mut MatchEngine| {
+        me.order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            19000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap()
+    };
+    let id = order(&mut me);
+    assert!((me.balance() - (1000.0 - 19.0 - 0.095)).abs() < 1e-9);
+    me.cancel(id);
+    assert!((me.balance() - 1000.0).abs() < 1e-9);
+
+    // 快照设置了手续费模型，恢复时需要重新设置
+    order(&mut me);
+    let snapshot = me.snapshot().unwrap();
+    assert!(MatchEngine::restore(&snapshot).is_err());
+    let mut me = MatchEngine::restore_fee(&snapshot, FeeSchedule::new(0.0002, 0.0005)).unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 20000.0,
+            low: 19000.0,
+            close: 19000.0,
+        },
+    );
+    me.update();
+    let record = me.position("BTC-USDT-SWAP").unwrap().log[0];
+    assert!(record.maker);
+    assert!((record.fee - 0.038).abs() < 1e-9);
+    assert!((me.balance() - (1000.0 - 19.0 - 0.038)).abs() < 1e-9);
+    assert!(MatchEngine::restore_fee(
+        MatchEngine::new(config).snapshot().unwrap(),
+        FeeSchedule::new(0.0, 0.0)
+    )
+    .is_err());
+}
+
+fn market(me: &mut MatchEngine, product: &str, side: Side) {
+    me.order(
+        product,
+        side,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+}
+
+fn flat(time: u64, price: f64) -> K {
+    K {
+        time,
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+    }
+}
+
+#[test]
+fn test_fee5() {
+    // 其他产品时间更晚的成交也计入过去 30 天的成交额
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("ETH-USDT-SWAP", 0.01, 0.0);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_fee(FeeSchedule::new(0.0, 0.001).tier(300.0, 0.0, 0.0))
+        .unwrap();
+    me.ready("ETH-USDT-SWAP", flat(2, 20000.0));
+    me.ready("BTC-USDT-SWAP", flat(1, 20000.0));
+    market(&mut me, "ETH-USDT-SWAP", Side::BuyLong);
+    market(&mut me, "BTC-USDT-SWAP", Side::BuyLong);
+    me.update();
+    me.ready("ETH-USDT-SWAP", flat(3, 20000.0));
+    me.ready("BTC-USDT-SWAP", flat(3, 20000.0));
+    market(&mut me, "BTC-USDT-SWAP", Side::BuySell);
+    me.update();
+    let log = &me.history()[0].log;
+    assert_eq!(log[0].fee, 0.2);
+    assert_eq!(log[1].fee, 0.0, "{:#?}", log);
+}
+
+#[test]
+fn test_fee6() {
+    // 强平价格和强平的手续费使用手续费模型的吃单费率
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let liquidation = |me: &mut MatchEngine| {
+        me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+        me.ready("BTC-USDT-SWAP", flat(1, 20000.0));
+        market(me, "BTC-USDT-SWAP", Side::BuyLong);
+        me.update();
+        let price = me.position("BTC-USDT-SWAP").unwrap().liquidation_price;
+        me.ready("BTC-USDT-SWAP", flat(2, 10000.0));
+        me.update();
+        price
+    };
+
+    let mut me = MatchEngine::new(config.close_fee(0.001));
+    let expected = liquidation(&mut me);
+    assert_eq!(me.history()[0].log[1].fee, 0.0);
+
+    let mut me = MatchEngine::new(config);
+    me.set_fee(FeeSchedule::new(0.0, 0.001)).unwrap();
+    assert_eq!(liquidation(&mut me), expected);
+    let position = &me.history()[0];
+    let record = position.log[1];
+    assert!((record.fee - expected * 0.01 * 0.001).abs() < 1e-12);
+    assert!((record.profit - record.fee + position.log[0].margin).abs() < 1e-9);
+}
@@ -0,0 +1,230 @@
+use auto_trading::*;
+
+/// 旧的数据在前面。
+fn data() -> Vec<f64> {
+    (0..80)
+        .map(|v| 100.0 + (v as f64 * 0.7).sin() * 10.0 + v as f64 * 0.3)
+        .collect()
+}
+
+/// 每次输入一个新的值，和对整个数据系列计算的结果比较。
+fn check<T, F>(mut indicator: T, mut f: F)
+where
+    T: Indicator<Input = f64, Output = f64>,
+    F: FnMut(&Source) -> f64,
+{
+    let data = data();
+
+    for i in 0..data.len() {
+        let result = indicator.update(data[i]);
+        let source = data[..=i].iter().rev().copied().collect::<Vec<_>>();
+        let expected = f(Source::new(&source));
+
+        assert!(
+            (result.is_nan() && expected.is_nan()) || (result - expected).abs() < 1e-9,
+            "index: {}, result: {}, expected: {}",
+            i,
+            result,
+            expected,
+        );
+        assert!(indicator.value().is_nan() == result.is_nan());
+    }
+}
+
+#[test]
+fn test_indicator1() {
+    check(SMA::new(14), |v| sma(v, 14));
+    check(EMA::new(14), |v| ema(v, 14));
+    check(RMA::new(14), |v| rma(v, 14));
+    check(CCI::new(20), |v| cci(v, 20));
+    check(RSI::new(14), |v| rsi(v, 14));
+    check(Highest::new(10), |v| highest(v, 10));
+    check(Lowest::new(10), |v| lowest(v, 10));
+}
+
+#[test]
+fn test_indicator2() {
+    let data = data();
+    let mut indicator = MACD::new(12, 26, 9);
+
+    for i in 0..data.len() {
+        let result = indicator.update(data[i]);
+        let source = data[..=i].iter().rev().copied().collect::<Vec<_>>();
+        let expected = macd(Source::new(&source), 12, 26, 9);
+
+        for (a, b) in [
+            (result.0, expected.0),
+            (result.1, expected.1),
+            (result.2, expected.2),
+        ] {
+            assert!(
+                (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-9,
+                "index: {}, result: {:?}, expected: {:?}",
+                i,
+                result,
+                expected,
+            );
+        }
+    }
+
+    assert!(!indicator.value().0.is_nan());
+}
+
+/// 收盘价，最高价，最低价，成交量，新的数据在前面。
+fn ohlcv() -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+    let close = (0..40)
+        .map(|v| 100.0 + (v as f64 * 0.45).sin() * 8.0 + v as f64 * 0.4)
+        .collect::<Vec<_>>();
+    let high = close
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + 1.0 + 0.5 * (i as f64).cos().abs())
+        .collect::<Vec<_>>();
+    let low = close
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v - 1.0 - 0.5 * (i as f64 * 1.3).sin().abs())
+        .collect::<Vec<_>>();
+    let volume = (0..40)
+        .map(|v| 1000.0 + 100.0 * (v % 7) as f64)
+        .collect::<Vec<_>>();
+    let rev = |v: Vec<f64>| v.into_iter().rev().collect::<Vec<_>>();
+    (rev(close), rev(high), rev(low), rev(volume))
+}
+
+fn assert_near(result: f64, expected: f64) {
+    assert!(
+        (result - expected).abs() < 1e-6,
+        "result: {}, expected: {}",
+        result,
+        expected,
+    );
+}
+
+#[test]
+fn test_indicator3() {
+    let (close, high, low, _) = ohlcv();
+    let (close, high, low) = (Source::new(&close), Source::new(&high), Source::new(&low));
+
+    let (basis, upper, lower) = bollinger(close, 20, 2.0);
+    assert_near(basis, 110.65943103975005);
+    assert_near(upper, 122.61890563531);
+    assert_near(lower, 98.6999564441901);
+    assert_near(wma(close, 9), 111.24816330079304);
+    assert_near(wma(&close[1..], 9), 112.67374818262009);
+    assert_near(hma(close, 9), 105.42605786131753);
+    assert_near(tr(high, low, close), 2.3438078792513437);
+    assert_near(
+        tr(&high[39..], &low[39..], &close[39..]),
+        high[39] - low[39],
+    );
+    assert_near(atr(high, low, close, 14), 3.620088579342836);
+    assert!(atr(&high[30..], &low[30..], &close[30..], 14).is_nan());
+}
+
+#[test]
+fn test_indicator4() {
+    let (close, high, low, _) = ohlcv();
+    let (close, high, low) = (Source::new(&close), Source::new(&high), Source::new(&low));
+
+    let (k, d) = stochastic(close, high, low, 14, 3, 3);
+    assert_near(k, 30.962676011446273);
+    assert_near(d, 38.964666549831165);
+
+    let (k, d) = stoch_rsi(close, 14, 14, 3, 3);
+    assert_near(k, 4.441092039022018);
+    assert_near(d, 13.45996514772529);
+
+    let (plus, minus, adx) = dmi(high, low, close, 14, 14);
+    assert_near(plus, 26.624545328574147);
+    assert_near(minus, 30.999868516368345);
+    assert_near(adx, 19.129041312722713);
+
+    assert_near(williams_r(close, high, low, 14), -74.0176694000791);
+}
+
+#[test]
+fn test_indicator5() {
+    let (close, high, low, volume) = ohlcv();
+    let hlc3 = (0..close.len())
+        .map(|i| (high[i] + low[i] + close[i]) / 3.0)
+        .collect::<Vec<_>>();
+    let (close, high, low) = (Source::new(&close), Source::new(&high), Source::new(&low));
+    let (hlc3, volume) = (Source::new(&hlc3), Source::new(&volume));
+
+    assert_near(vwap(hlc3, volume, 10), 114.51360053936743);
+    assert_near(obv(close, volume), 4300.0);
+    assert_near(mfi(hlc3, volume, 14), 57.65205876399159);
+
+    let (middle, upper, lower) = donchian(high, low, 20);
+    assert_near(middle, 111.29387889098379);
+    assert_near(upper, 121.94237389264747);
+    assert_near(lower, 100.64538388932013);
+
+    let (middle, upper, lower) = keltner(close, high, low, 20, 2.0);
+    assert_near(middle, 110.76516366542313);
+    assert_near(upper, 117.88849911404179);
+    assert_near(lower, 103.64182821680447);
+}
+
+#[test]
+fn test_indicator6() {
+    let (close, high, low, _) = ohlcv();
+    let (close, high, low) = (Source::new(&close), Source::new(&high), Source::new(&low));
+
+    let (value, direction) = supertrend(close, high, low, 3.0, 10);
+    assert_near(value, 118.50551848190545);
+    assert_eq!(direction, 1.0);
+
+    assert_near(sar(close, high, low, 0.02, 0.02, 0.2), 120.19238306395224);
+
+    let (conversion, base, span_a, span_b) = ichimoku(high, low, 9, 26, 30);
+    assert_near(conversion, 113.94457489692502);
+    assert_near(base, 111.29387889098379);
+    assert_near(span_a, 112.6192268939544);
+    assert_near(span_b, 108.45602471645668);
+    assert!(ichimoku(high, low, 9, 26, 52).3.is_nan());
+}
+
+#[test]
+fn test_indicator7() {
+    // 窗口里的 NaN 会被忽略，和逐个比较窗口里的值的结果一致
+    let data = [
+        1.0,
+        f64::NAN,
+        3.0,
+        2.0,
+        f64::NAN,
+        f64::NAN,
+        f64::NAN,
+        0.5,
+        4.0,
+    ];
+    let mut high = Highest::new(3);
+    let mut low = Lowest::new(3);
+
+    for i in 0..data.len() {
+        let window = &data[(i + 1).saturating_sub(3)..=i];
+        let full = i + 1 >= 3;
+
+        for (result, expected) in [
+            (
+                high.update(data[i]),
+                window.iter().copied().fold(f64::NAN, f64::max),
+            ),
+            (
+                low.update(data[i]),
+                window.iter().copied().fold(f64::NAN, f64::min),
+            ),
+        ] {
+            let expected = if full { expected } else { f64::NAN };
+            assert!(
+                (result.is_nan() && expected.is_nan()) || result == expected,
+                "index: {}, result: {}, expected: {}",
+                i,
+                result,
+                expected,
+            );
+        }
+    }
+}
@@ -1143,3 +1143,1975 @@ fn test_update11() {
         me
     );
 }
+
+#[test]
+fn test_tier1() {
+    // 测试风险档位的最大杠杆
+    let config = Config::new().initial_margin(1000.0).lever(100);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_tier(
+        "BTC-USDT-SWAP",
+        vec![
+            Tier {
+                max_notional: 100.0,
+                maintenance: 0.004,
+                max_lever: 125,
+                maintenance_amount: 0.0,
+            },
+            Tier {
+                max_notional: 1000.0,
+                maintenance: 0.01,
+                max_lever: 50,
+                maintenance_amount: 0.6,
+            },
+        ],
+    )
+    .unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    // 仓位价值 200，位于第二档，杠杆 100 超过最大杠杆 50
+    let result = me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    );
+    assert!(result.is_err(), "{:#?}", me);
+    // 仓位价值 2000，超过最后一档
+    let result = me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Quantity(0.1),
+        Unit::Quantity(100.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    );
+    assert!(result.is_err(), "{:#?}", me);
+    assert!(me.balance() == 1000.0, "{:#?}", me);
+}
+
+#[test]
+fn test_tier2() {
+    // 测试风险档位的强平价格
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .lever(100)
+        .open_fee(0.0002)
+        .close_fee(0.0005)
+        .maintenance(0.004);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.set_tier(
+        "BTC-USDT-SWAP",
+        vec![
+            Tier {
+                max_notional: 100.0,
+                maintenance: 0.004,
+                max_lever: 125,
+                maintenance_amount: 0.0,
+            },
+            Tier {
+                max_notional: 1000.0,
+                maintenance: 0.01,
+                max_lever: 100,
+                maintenance_amount: 0.6,
+            },
+        ],
+    )
+    .unwrap();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    // 20000 * (1 - 0.01 + 0.01) - 0.6 / 0.01 + 20000 * 0.01 * 0.0005
+    assert!(
+        (me.position("BTC-USDT-SWAP").unwrap().liquidation_price - 19940.1).abs() < 1e-6,
+        "{:#?}",
+        me
+    );
+    // 档位必须从小到大排列
+    assert!(me
+        .set_tier(
+            "BTC-USDT-SWAP",
+            vec![
+                Tier {
+                    max_notional: 1000.0,
+                    maintenance: 0.01,
+                    max_lever: 100,
+                    maintenance_amount: 0.6,
+                },
+                Tier {
+                    max_notional: 100.0,
+                    maintenance: 0.004,
+                    max_lever: 125,
+                    maintenance_amount: 0.0,
+                },
+            ],
+        )
+        .is_err());
+}
+
+#[test]
+fn test_spot1() {
+    // 现货不能做空，不能设置保证金
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_spot_product("BTC-USDT", 0.0001, 0.0);
+    me.ready(
+        "BTC-USDT",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    assert!(me
+        .order(
+            "BTC-USDT",
+            Side::SellShort,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+    assert!(me
+        .order(
+            "BTC-USDT",
+            Side::BuyLong,
+            0.0,
+            Unit::Ignore,
+            Unit::Quantity(20.0),
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+    // 没有杠杆，占用全部成交额
+    me.order(
+        "BTC-USDT",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert!(me.balance() == 800.0, "{:#?}", me);
+    me.cancel(1);
+    assert!(me.balance() == 1000.0, "{:#?}", me);
+}
+
+#[test]
+fn test_spot2() {
+    // 手续费从收到的资产中扣除
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .open_fee(0.001)
+        .close_fee(0.001);
+    let mut me = MatchEngine::new(config);
+    me.insert_spot_product("BTC-USDT", 0.0001, 0.0);
+    me.ready(
+        "BTC-USDT",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert!(me.balance() == 800.0, "{:#?}", me);
+    assert!((me.asset("BTC") - 0.00999).abs() < 1e-12, "{:#?}", me);
+    let position = me.position("BTC-USDT").unwrap();
+    assert!(position.liquidation_price == 0.0, "{:#?}", me);
+    assert!((position.quantity - 0.00999).abs() < 1e-12, "{:#?}", me);
+    me.ready(
+        "BTC-USDT",
+        K {
+            time: 2,
+            open: 30000.0,
+            high: 30000.0,
+            low: 30000.0,
+            close: 30000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT",
+        Side::BuySell,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    // 800 + 30000 * 0.00999 * (1 - 0.001)
+    assert!((me.balance() - 1099.4003).abs() < 1e-6, "{:#?}", me);
+    assert!(me.asset("BTC").abs() < 1e-12, "{:#?}", me);
+    assert!(me.position("BTC-USDT").is_none(), "{:#?}", me);
+    assert!(me.asset_history().len() == 2, "{:#?}", me);
+    assert!(me.asset_history()[0].0 == 1, "{:#?}", me);
+    assert!(me.asset_history()[1].0 == 2, "{:#?}", me);
+}
+
+#[test]
+fn test_spot3() {
+    // 交割合约和期权不是现货
+    assert!(instrument("BTC-USDT") == Instrument::Spot);
+    assert!(instrument("BTC-USDT-SWAP") == Instrument::Swap);
+    assert!(instrument("BTCUSDTSWAP") == Instrument::Swap);
+    assert!(instrument("BTC-USD-231229") == Instrument::Futures);
+    assert!(instrument("BTC-USD-231229-30000-C") == Instrument::Option);
+    assert!(is_spot("BTC-USDT"));
+    assert!(!is_spot("BTC-USD-231229"));
+    assert!(!is_spot("BTC-USD-231229-30000-P"));
+}
+
+/// 开多仓后，在同时触发止盈和止损的 k 线中，返回平仓价格。
+fn price_path_close_price(path: PricePath, lower: Vec<K>) -> f64 {
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .margin(Unit::Quantity(100.0))
+        .lever(10)
+        .path(path);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Quantity(19000.0),
+        Unit::Quantity(21000.0),
+        Unit::Quantity(19000.0),
+    )
+    .unwrap();
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_some(), "{:#?}", me);
+    me.ready_lower(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21500.0,
+            low: 18800.0,
+            close: 20000.0,
+        },
+        lower,
+    );
+    me.update();
+    assert!(me.history().len() == 1, "{:#?}", me);
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    me.history()[0].close_price
+}
+
+#[test]
+fn test_price_path1() {
+    assert!(price_path_close_price(PricePath::OpenHighLowClose, Vec::new()) == 21000.0);
+    assert!(price_path_close_price(PricePath::OpenLowHighClose, Vec::new()) == 19000.0);
+    // 最低价离开盘价更近
+    assert!(price_path_close_price(PricePath::Nearest, Vec::new()) == 19000.0);
+    // 多仓先到达最低价
+    assert!(price_path_close_price(PricePath::Pessimistic, Vec::new()) == 19000.0);
+}
+
+#[test]
+fn test_price_path2() {
+    // 小级别 k 线先到达最高价
+    let lower = vec![
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21500.0,
+            low: 20000.0,
+            close: 21200.0,
+        },
+        K {
+            time: 3,
+            open: 21200.0,
+            high: 21200.0,
+            low: 18800.0,
+            close: 20000.0,
+        },
+    ];
+    assert!(price_path_close_price(PricePath::Lower(Level::Minute1), lower) == 21000.0);
+    // 没有小级别 k 线
+    assert!(price_path_close_price(PricePath::Lower(Level::Minute1), Vec::new()) == 19000.0);
+}
+
+fn price_path_pending(path: PricePath, side: Side) -> usize {
+    let (price, profit) = if side == Side::BuyLong {
+        (19500.0, 21000.0)
+    } else {
+        (20500.0, 19000.0)
+    };
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .margin(Unit::Quantity(100.0))
+        .lever(10)
+        .path(path);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        side,
+        price,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(profit),
+        Unit::Ignore,
+        Unit::Quantity(profit),
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21500.0,
+            low: 18500.0,
+            close: 20000.0,
+        },
+    );
+    me.update();
+    me.history().len()
+}
+
+#[test]
+fn test_price_path3() {
+    // 没有仓位时按照挂单方向，买入挂单先到达最高价，成交之后不会再触发止盈
+    assert!(price_path_pending(PricePath::OpenLowHighClose, Side::BuyLong) == 1);
+    assert!(price_path_pending(PricePath::Pessimistic, Side::BuyLong) == 0);
+    // 卖出挂单先到达最低价
+    assert!(price_path_pending(PricePath::OpenHighLowClose, Side::SellShort) == 1);
+    assert!(price_path_pending(PricePath::Pessimistic, Side::SellShort) == 0);
+}
+
+#[test]
+fn test_event1() {
+    // 委托，成交，触发，平仓
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    let rx = me.subscribe();
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    assert!(me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuySell,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21500.0,
+            low: 20000.0,
+            close: 21000.0,
+        },
+    );
+    me.update();
+    let event = rx.try_iter().collect::<Vec<_>>();
+    assert!(
+        matches!(
+            event.as_slice(),
+            [
+                Event::Reject { time: 1, .. },
+                Event::Accept { id: 1, time: 1, .. },
+                Event::Fill { id: 1, .. },
+                Event::Open { time: 1, .. },
+                Event::Trigger {
+                    id: 1,
+                    time: 2,
+                    delegate: Delegate {
+                        price: Price::GreaterThanMarket(_),
+                        ..
+                    },
+                    ..
+                },
+                Event::Fill { id: 1, .. },
+                Event::Close { time: 2, .. },
+            ]
+        ),
+        "{:#?}",
+        event
+    );
+    if let Event::Close { position, .. } = &event[6] {
+        assert!(position.close_price == 21000.0, "{:#?}", event);
+    }
+}
+
+#[test]
+fn test_event2() {
+    // 撤单，强平
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(100)
+        .maintenance(0.004);
+    let mut me = MatchEngine::new(config);
+    let rx = me.subscribe();
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    let id = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            10000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    me.cancel(id);
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 20000.0,
+            low: 15000.0,
+            close: 15000.0,
+        },
+    );
+    me.update();
+    let event = rx.try_iter().collect::<Vec<_>>();
+    assert!(
+        matches!(
+            event.as_slice(),
+            [
+                Event::Accept { id: 1, .. },
+                Event::Cancel { id: 1, time: 1, .. },
+                Event::Accept { id: 2, .. },
+                Event::Fill { id: 2, .. },
+                Event::Open { .. },
+                Event::Liquidate { time: 2, .. },
+            ]
+        ),
+        "{:#?}",
+        event
+    );
+    // 接收端被丢弃后取消订阅
+    drop(rx);
+    me.update();
+}
+
+#[test]
+fn test_amend1() {
+    // 修改价格和数量，委托 id 和止盈不变
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .margin(Unit::Quantity(100.0))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        19000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    let id = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            18000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    assert!(me.balance() == 800.0, "{:#?}", me);
+    me.amend(
+        1,
+        19500.0,
+        Unit::Quantity(0.02),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    // 保证金按照比例变成 100 / 190 * 390
+    assert!(
+        (me.balance() - (900.0 - 100.0 / 190.0 * 390.0)).abs() < 1e-9,
+        "{:#?}",
+        me
+    );
+    match me.delegate(1).unwrap() {
+        DelegateState::OpenProfit(a, b) => {
+            assert!(a.price == Price::LessThanMarket(19500.0), "{:#?}", me);
+            assert!(a.quantity == 0.02, "{:#?}", me);
+            assert!((a.margin - 100.0 / 190.0 * 390.0).abs() < 1e-9, "{:#?}", me);
+            assert!(b.price == Price::GreaterThanMarket(21000.0), "{:#?}", me);
+            assert!(b.quantity == 0.02, "{:#?}", me);
+        }
+        _ => panic!("{:#?}", me),
+    }
+    // 新的委托 id 不受影响
+    let new_id = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            17000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    assert!(new_id == id + 1, "{:#?}", me);
+}
+
+#[test]
+fn test_amend2() {
+    // 检查失败时委托保持不变
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .margin(Unit::Quantity(100.0))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        19000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    let delegate = me.delegate(1).unwrap();
+    // 止盈触发价小于委托价格
+    assert!(me
+        .amend(
+            1,
+            22000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore
+        )
+        .is_err());
+    assert!(me.delegate(1).unwrap() == delegate, "{:#?}", me);
+    assert!(me.balance() == 900.0, "{:#?}", me);
+    assert!(me
+        .amend(
+            2,
+            19000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore
+        )
+        .is_err());
+    // 成交后修改止盈委托
+    me.amend(
+        1,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_some(), "{:#?}", me);
+    // 止盈委托没有单独的委托价格，修改触发价格
+    assert!(me
+        .amend(
+            1,
+            21500.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore
+        )
+        .is_err());
+    me.amend(
+        1,
+        0.0,
+        Unit::Ignore,
+        Unit::Quantity(21500.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert!(
+        matches!(
+            me.delegate(1).unwrap(),
+            DelegateState::Single(Delegate {
+                side: Side::BuySell,
+                price: Price::GreaterThanMarket(v),
+                quantity: v2,
+                ..
+            }) if v == 21500.0 && v2 == 0.01
+        ),
+        "{:#?}",
+        me
+    );
+}
+
+#[test]
+fn test_amend3() {
+    // 成交后修改止盈止损，修改反手委托
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .margin(Unit::Quantity(100.0))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(22000.0),
+        Unit::Quantity(19000.0),
+        Unit::Quantity(22000.0),
+        Unit::Quantity(18900.0),
+    )
+    .unwrap();
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_some(), "{:#?}", me);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 20800.0,
+            low: 20000.0,
+            close: 20800.0,
+        },
+    );
+    me.update();
+    let amend = |me: &mut MatchEngine, price, condition| {
+        me.amend(
+            1,
+            price,
+            Unit::Ignore,
+            Unit::Ignore,
+            condition,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+    };
+    // 止盈止损没有委托价格
+    assert!(amend(&mut me, 20500.0, Unit::Quantity(20500.0)).is_err());
+    // 止损触发价格已经被触发
+    assert!(amend(&mut me, 0.0, Unit::Quantity(21000.0)).is_err());
+    // 把止损移动到开仓价格之上，止盈不变
+    amend(&mut me, 0.0, Unit::Quantity(20500.0)).unwrap();
+    assert!(
+        matches!(
+            me.delegate(1).unwrap(),
+            DelegateState::ProfitLoss(
+                Delegate {
+                    side: Side::BuySell,
+                    price: Price::GreaterThanLimit(a, b),
+                    ..
+                },
+                Delegate {
+                    side: Side::BuySell,
+                    price: Price::LessThanLimit(c, d),
+                    quantity,
+                    ..
+                }
+            ) if a == 22000.0 && b == 22000.0 && c == 20500.0 && d == 18900.0 && quantity == 0.01
+        ),
+        "{:#?}",
+        me
+    );
+    // 反手委托
+    let id = me
+        .reverse(
+            "BTC-USDT-SWAP",
+            Side::SellShort,
+            21500.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    me.amend(
+        id,
+        21600.0,
+        Unit::Ignore,
+        Unit::Quantity(20000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert!(
+        matches!(
+            me.delegate(id).unwrap(),
+            DelegateState::HedgingProfit(
+                Delegate {
+                    side: Side::BuySell,
+                    price: Price::GreaterThanMarket(a),
+                    quantity,
+                    ..
+                },
+                Delegate {
+                    side: Side::SellShort,
+                    price: Price::GreaterThanMarket(b),
+                    ..
+                },
+                Delegate {
+                    side: Side::SellLong,
+                    price: Price::LessThanMarket(c),
+                    ..
+                }
+            ) if a == 21600.0 && b == 21600.0 && c == 20000.0 && quantity == 0.01
+        ),
+        "{:#?}",
+        me
+    );
+}
+
+#[test]
+fn test_amend4() {
+    // 成交后修改市价的止损，普通的平仓委托不能设置止盈止损
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .margin(Unit::Quantity(100.0))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    let k = |time, low, close| K {
+        time,
+        open: close,
+        high: close,
+        low,
+        close,
+    };
+    me.ready("BTC-USDT-SWAP", k(1, 20000.0, 20000.0));
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(19000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_some(), "{:#?}", me);
+    me.amend(
+        1,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(19500.0),
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert!(
+        matches!(
+            me.delegate(1).unwrap(),
+            DelegateState::Single(Delegate {
+                side: Side::BuySell,
+                price: Price::LessThanMarket(v),
+                ..
+            }) if v == 19500.0
+        ),
+        "{:#?}",
+        me
+    );
+    // 止损没有被替换成市价平仓
+    me.ready("BTC-USDT-SWAP", k(2, 19800.0, 20000.0));
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_some(), "{:#?}", me);
+
+    let id = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuySell,
+            21000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    assert!(me
+        .amend(
+            id,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Quantity(19500.0),
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+    me.cancel(id);
+
+    me.ready("BTC-USDT-SWAP", k(3, 19400.0, 19600.0));
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none(), "{:#?}", me);
+    assert_eq!(me.history()[0].close_price, 19500.0);
+}
+
+#[test]
+fn test_order_record1() {
+    // 委托失败，成交，止盈
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    assert!(me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuySell,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert_eq!(me.order_records()[0].id, 0);
+    assert_eq!(me.order_records()[0].status, OrderStatus::Rejected);
+    assert!(!me.order_records()[0].reason.is_empty());
+    assert_eq!(me.order_record(1).unwrap().status, OrderStatus::New);
+    me.update();
+    let record = me.order_record(1).unwrap();
+    assert_eq!(record.status, OrderStatus::Filled);
+    assert_eq!(record.fill_quantity, 0.01);
+    assert_eq!(record.fill_price, 20000.0);
+    assert_eq!(record.fill_time, 1);
+    assert_eq!(record.stop_quantity, 0.0);
+    // 全部成交后不再是工作中的委托，止盈委托仍然存在
+    assert!(me.open_orders("BTC-USDT-SWAP").is_empty());
+    assert!(me.delegate(1).is_some());
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21500.0,
+            low: 20000.0,
+            close: 21000.0,
+        },
+    );
+    me.update();
+    let record = me.order_record(1).unwrap();
+    assert_eq!(record.status, OrderStatus::Filled);
+    assert_eq!(record.fill_price, 20000.0);
+    assert_eq!(record.fill_time, 1);
+    // 止盈的成交
+    assert_eq!(record.stop_quantity, 0.01);
+    assert_eq!(record.stop_price, 21000.0);
+    assert_eq!(record.stop_time, 2);
+    assert!(me.open_orders("BTC-USDT-SWAP").is_empty());
+}
+
+#[test]
+fn test_order_record2() {
+    // 修改，撤销，失效
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    let id = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            19000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    me.update();
+    assert_eq!(me.open_orders("BTC-USDT-SWAP")[0].id, id);
+    me.amend(
+        id,
+        18000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    assert_eq!(me.order_record(id).unwrap().price, 18000.0);
+    assert_eq!(me.order_record(id).unwrap().status, OrderStatus::New);
+    me.cancel(id);
+    assert_eq!(me.order_record(id).unwrap().status, OrderStatus::Cancelled);
+    assert!(me.open_orders("BTC-USDT-SWAP").is_empty());
+
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    let close = me
+        .order(
+            "BTC-USDT-SWAP",
+            Side::BuySell,
+            25000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuySell,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    me.update();
+    assert_eq!(me.order_record(close).unwrap().status, OrderStatus::Expired);
+    assert!(me.open_orders("BTC-USDT-SWAP").is_empty());
+}
+
+#[test]
+fn test_order_record3() {
+    // 反方向的开仓委托同时成交，拆分出来的平仓委托使用新的 id，成交记录到原委托
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    let rx = me.subscribe();
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    for (side, price, quantity) in [(Side::BuyLong, 0.0, 0.01), (Side::SellShort, 20000.0, 0.02)] {
+        me.order(
+            "BTC-USDT-SWAP",
+            side,
+            price,
+            Unit::Quantity(quantity),
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    }
+    me.update();
+    let event = rx.try_iter().collect::<Vec<_>>();
+    let split = event
+        .iter()
+        .filter_map(|v| match v {
+            Event::Split { id, parent, .. } => Some((*id, *parent)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(split, vec![(3, 2)], "{:#?}", event);
+    assert!(event
+        .iter()
+        .any(|v| matches!(v, Event::Fill { id: 3, record, .. } if record.side == Side::BuySell)));
+    assert_eq!(me.history().len(), 1);
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.side, Side::SellShort);
+    assert_eq!(position.quantity, 0.01);
+    let record = me.order_record(2).unwrap();
+    assert_eq!(record.status, OrderStatus::Filled);
+    assert_eq!(record.fill_quantity, 0.02);
+    assert!(me.order_record(3).is_none());
+}
+
+#[test]
+fn test_snapshot1() {
+    // 保存后恢复，继续撮合的结果和没有中断的结果一致
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10)
+        .open_fee(0.0002)
+        .close_fee(0.0005);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Quantity(21000.0),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        19000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    let mut restore = MatchEngine::restore(me.snapshot().unwrap()).unwrap();
+    assert_eq!(restore.balance(), me.balance());
+    assert_eq!(restore.delegate(2), me.delegate(2));
+    assert_eq!(restore.order_records(), me.order_records());
+    let rx = restore.subscribe();
+    for me in [&mut me, &mut restore] {
+        me.ready(
+            "BTC-USDT-SWAP",
+            K {
+                time: 2,
+                open: 20000.0,
+                high: 21500.0,
+                low: 18500.0,
+                close: 21000.0,
+            },
+        );
+        me.update();
+    }
+    assert!(rx.try_iter().count() > 0);
+    assert_eq!(restore.balance(), me.balance());
+    assert_eq!(
+        restore.position("BTC-USDT-SWAP").map(|v| v.quantity),
+        me.position("BTC-USDT-SWAP").map(|v| v.quantity)
+    );
+    assert_eq!(restore.history().len(), me.history().len());
+    assert_eq!(restore.order_records(), me.order_records());
+    assert_eq!(
+        restore
+            .order(
+                "BTC-USDT-SWAP",
+                Side::BuyLong,
+                0.0,
+                Unit::Ignore,
+                Unit::Ignore,
+                Unit::Ignore,
+                Unit::Ignore,
+                Unit::Ignore,
+                Unit::Ignore,
+            )
+            .unwrap(),
+        me.order(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_snapshot2() {
+    // 保存到文件
+    let mut me = MatchEngine::new(
+        Config::new()
+            .initial_margin(1000.0)
+            .path(PricePath::Lower(Level::Minute1)),
+    );
+    me.insert_spot_product("BTC-USDT", 0.0001, 0.0);
+    let path = std::env::temp_dir().join("auto_trading_test_snapshot2.json");
+    me.save(&path).unwrap();
+    let restore = MatchEngine::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restore.balance(), 1000.0);
+    assert_eq!(restore.asset("USDT"), me.asset("USDT"));
+    assert!(MatchEngine::restore("{}").is_err());
+}
+
+#[test]
+fn test_reduce_only1() {
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+
+    // 没有仓位
+    assert!(me
+        .reduce_only("BTC-USDT-SWAP", Side::SellShort, 0.0, Unit::Ignore)
+        .is_err());
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Quantity(0.02),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+
+    // 同方向不能减仓
+    assert!(me
+        .reduce_only("BTC-USDT-SWAP", Side::BuyLong, 0.0, Unit::Ignore)
+        .is_err());
+
+    // 委托数量超过持仓数量时按照持仓数量委托
+    let id = me
+        .reduce_only("BTC-USDT-SWAP", Side::SellShort, 0.0, Unit::Quantity(0.05))
+        .unwrap();
+    assert!(matches!(
+        me.delegate(id),
+        Some(DelegateState::Single(Delegate {
+            side: Side::BuySell,
+            quantity,
+            ..
+        })) if quantity == 0.02
+    ));
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none());
+    assert_eq!(me.balance(), 1000.0);
+    assert_eq!(me.history().len(), 1);
+}
+
+#[test]
+fn test_reduce_only2() {
+    // 成交前仓位被减少，只减仓委托失效
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.02))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::SellShort,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    let id = me
+        .reduce_only("BTC-USDT-SWAP", Side::BuyLong, 19000.0, Unit::Ignore)
+        .unwrap();
+    me.reduce_only("BTC-USDT-SWAP", Side::BuyLong, 0.0, Unit::Proportion(0.5))
+        .unwrap();
+    me.update();
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().quantity, 0.01);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 20000.0,
+            low: 18000.0,
+            close: 18000.0,
+        },
+    );
+    me.update();
+    assert_eq!(me.delegate(id), None);
+    assert_eq!(me.order_record(id).unwrap().status, OrderStatus::Expired);
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().quantity, 0.01);
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().side, Side::SellShort);
+}
+
+#[test]
+fn test_reverse1() {
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+
+    // 没有仓位时直接开仓
+    me.reverse(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Quantity(0.02),
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().quantity, 0.02);
+
+    // 同方向不能反手
+    assert!(me
+        .reverse(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .is_err());
+
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21000.0,
+            low: 20000.0,
+            close: 21000.0,
+        },
+    );
+    let id = me
+        .reverse(
+            "BTC-USDT-SWAP",
+            Side::SellShort,
+            0.0,
+            Unit::Quantity(0.01),
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Quantity(22000.0),
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    assert!(matches!(
+        me.delegate(id),
+        Some(DelegateState::HedgingLoss(
+            Delegate {
+                side: Side::BuySell,
+                ..
+            },
+            Delegate {
+                side: Side::SellShort,
+                ..
+            },
+            _
+        ))
+    ));
+    assert_eq!(me.balance(), 1000.0 - 40.0 - 21.0);
+    me.update();
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.side, Side::SellShort);
+    assert_eq!(position.quantity, 0.01);
+    assert_eq!(position.open_price, 21000.0);
+    assert_eq!(me.history().len(), 1);
+    assert_eq!(me.history()[0].profit, 20.0);
+    assert_eq!(me.balance(), 1000.0 + 20.0 - 21.0);
+    let record = me.order_record(id).unwrap();
+    assert_eq!(record.side, Side::SellShort);
+    assert_eq!(record.quantity, 0.03);
+    assert_eq!(record.fill_quantity, 0.03);
+    assert_eq!(record.status, OrderStatus::Filled);
+
+    // 止损还在工作
+    assert!(matches!(
+        me.delegate(id),
+        Some(DelegateState::Single(Delegate {
+            side: Side::SellLong,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn test_reverse2() {
+    // 撤销反手委托，退还开仓的保证金
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::SellShort,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    let id = me
+        .reverse(
+            "BTC-USDT-SWAP",
+            Side::BuyLong,
+            21000.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    assert_eq!(me.balance(), 1000.0 - 20.0 - 21.0);
+    me.update();
+    assert_eq!(me.position("BTC-USDT-SWAP").unwrap().side, Side::SellShort);
+    assert!(me.cancel(id));
+    assert_eq!(me.balance(), 1000.0 - 20.0);
+    assert_eq!(me.order_record(id).unwrap().status, OrderStatus::Cancelled);
+}
+
+#[test]
+fn test_reverse3() {
+    // 余额不足以单独开仓，计入平仓释放的保证金后可以反手
+    let config = Config::new()
+        .initial_margin(100.0)
+        .quantity(Unit::Quantity(0.004))
+        .lever(1);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.001, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::SellShort,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert_eq!(me.balance(), 20.0);
+    // 按比例向下取整为 0
+    assert!(me
+        .reduce_only("BTC-USDT-SWAP", Side::BuyLong, 0.0, Unit::Proportion(0.1))
+        .is_err());
+    me.reverse(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.side, Side::BuyLong);
+    assert_eq!(position.quantity, 0.004);
+    assert_eq!(me.balance(), 20.0);
+}
+
+#[test]
+fn test_adjust_margin1() {
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    assert!(me.adjust_margin("BTC-USDT-SWAP", 10.0).is_err());
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert_eq!(
+        me.position("BTC-USDT-SWAP").unwrap().liquidation_price,
+        18000.0
+    );
+
+    // 先挂平仓委托，调整保证金后平仓委托返还的保证金同步调整
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuySell,
+        21000.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.adjust_margin("BTC-USDT-SWAP", 10.0).unwrap();
+    assert_eq!(me.balance(), 970.0);
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.margin, 30.0);
+    assert_eq!(position.liquidation_price, 17000.0);
+
+    // 不能超过余额
+    assert!(me.adjust_margin("BTC-USDT-SWAP", 1000.0).is_err());
+
+    // 不能小于仓位价值除以杠杆
+    assert!(me.adjust_margin("BTC-USDT-SWAP", -20.0).is_err());
+    me.adjust_margin("BTC-USDT-SWAP", -5.0).unwrap();
+    assert_eq!(me.balance(), 975.0);
+    assert_eq!(
+        me.position("BTC-USDT-SWAP").unwrap().liquidation_price,
+        17500.0
+    );
+
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 21000.0,
+            low: 20000.0,
+            close: 21000.0,
+        },
+    );
+    me.update();
+    assert!(me.position("BTC-USDT-SWAP").is_none());
+    assert_eq!(me.balance(), 1010.0);
+}
+
+#[test]
+fn test_adjust_margin2() {
+    // 强平价格不能越过当前价格
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .margin(Unit::Quantity(100.0))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    let rx = me.subscribe();
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::SellShort,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+    assert_eq!(
+        me.position("BTC-USDT-SWAP").unwrap().liquidation_price,
+        30000.0
+    );
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 2,
+            open: 20000.0,
+            high: 25000.0,
+            low: 20000.0,
+            close: 25000.0,
+        },
+    );
+    me.update();
+    assert!(me.adjust_margin("BTC-USDT-SWAP", -60.0).is_err());
+    me.adjust_margin("BTC-USDT-SWAP", -40.0).unwrap();
+    assert_eq!(
+        me.position("BTC-USDT-SWAP").unwrap().liquidation_price,
+        26000.0
+    );
+    assert!(matches!(
+        rx.try_iter().last(),
+        Some(Event::Margin { time: 2, .. })
+    ));
+}
+
+#[test]
+fn test_lever1() {
+    // 不同产品使用不同的杠杆
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.insert_product("ETH-USDT-SWAP", 0.01, 0.0);
+    me.set_lever("ETH-USDT-SWAP", 20).unwrap();
+    assert_eq!(me.lever("BTC-USDT-SWAP"), Some(10));
+    assert_eq!(me.lever("ETH-USDT-SWAP"), Some(20));
+    assert!(me.set_lever("ETH-USDT-SWAP", 0).is_err());
+    assert!(me.set_lever("XRP-USDT-SWAP", 10).is_err());
+    for product in ["BTC-USDT-SWAP", "ETH-USDT-SWAP"] {
+        me.ready(
+            product,
+            K {
+                time: 1,
+                open: 20000.0,
+                high: 20000.0,
+                low: 20000.0,
+                close: 20000.0,
+            },
+        );
+        me.order(
+            product,
+            Side::BuyLong,
+            0.0,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+            Unit::Ignore,
+        )
+        .unwrap();
+    }
+    assert_eq!(me.balance(), 1000.0 - 20.0 - 10.0);
+    me.update();
+    let btc = me.position("BTC-USDT-SWAP").unwrap();
+    let eth = me.position("ETH-USDT-SWAP").unwrap();
+    assert_eq!(
+        (btc.lever, btc.margin, btc.liquidation_price),
+        (10, 20.0, 18000.0)
+    );
+    assert_eq!(
+        (eth.lever, eth.margin, eth.liquidation_price),
+        (20, 10.0, 19000.0)
+    );
+}
+
+#[test]
+fn test_lever2() {
+    // 持仓时修改杠杆
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let mut me = MatchEngine::new(config);
+    me.insert_product("BTC-USDT-SWAP", 0.01, 0.0);
+    me.insert_spot_product("BTC-USDT", 0.0001, 0.0);
+    assert_eq!(me.lever("BTC-USDT"), Some(1));
+    assert!(me.set_lever("BTC-USDT", 10).is_err());
+    me.ready(
+        "BTC-USDT-SWAP",
+        K {
+            time: 1,
+            open: 20000.0,
+            high: 20000.0,
+            low: 20000.0,
+            close: 20000.0,
+        },
+    );
+    me.order(
+        "BTC-USDT-SWAP",
+        Side::BuyLong,
+        0.0,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+        Unit::Ignore,
+    )
+    .unwrap();
+    me.update();
+
+    // 保证金不足以维持 5 倍杠杆的仓位价值
+    assert!(me.set_lever("BTC-USDT-SWAP", 5).is_err());
+    assert_eq!(me.lever("BTC-USDT-SWAP"), Some(10));
+
+    // 提高杠杆，多出的保证金作为追加保证金，强平价格不变
+    me.set_lever("BTC-USDT-SWAP", 20).unwrap();
+    let position = me.position("BTC-USDT-SWAP").unwrap();
+    assert_eq!(position.lever, 20);
+    assert_eq!(position.liquidation_price, 18000.0);
+    me.adjust_margin("BTC-USDT-SWAP", -10.0).unwrap();
+    assert_eq!(
+        me.position("BTC-USDT-SWAP").unwrap().liquidation_price,
+        19000.0
+    );
+
+    // 超过风险档位的最大杠杆
+    me.set_tier(
+        "BTC-USDT-SWAP",
+        vec![Tier {
+            max_notional: 1000.0,
+            maintenance: 0.0,
+            max_lever: 50,
+            maintenance_amount: 0.0,
+        }],
+    )
+    .unwrap();
+    assert!(me.set_lever("BTC-USDT-SWAP", 75).is_err());
+    me.set_lever("BTC-USDT-SWAP", 50).unwrap();
+}
@@ -0,0 +1,137 @@
+use auto_trading::*;
+
+/// 开盘价，最高价，最低价，收盘价，旧的数据在前面。
+fn detect(k: &[(f64, f64, f64, f64)]) -> Vec<PatternSignal> {
+    let column = |f: fn(&(f64, f64, f64, f64)) -> f64| k.iter().rev().map(f).collect::<Vec<_>>();
+    let (open, high, low, close) = (
+        column(|v| v.0),
+        column(|v| v.1),
+        column(|v| v.2),
+        column(|v| v.3),
+    );
+    patterns(
+        Source::new(&open),
+        Source::new(&high),
+        Source::new(&low),
+        Source::new(&close),
+    )
+}
+
+fn find(signal: &[PatternSignal], pattern: Pattern) -> Option<PatternSignal> {
+    signal.iter().find(|v| v.pattern == pattern).copied()
+}
+
+#[test]
+fn test_pattern1() {
+    // 看涨吞没
+    let signal = detect(&[(105.0, 106.0, 101.0, 102.0), (101.0, 108.0, 100.0, 107.0)]);
+    let v = find(&signal, Pattern::Engulfing).unwrap();
+    assert_eq!(v.direction, Direction::Bullish);
+    assert!((v.strength - 0.5).abs() < 1e-9);
+    assert_eq!(
+        find(&signal, Pattern::OutsideBar).unwrap().direction,
+        Direction::Bullish
+    );
+
+    // 看跌吞没
+    let signal = detect(&[(102.0, 106.0, 101.0, 105.0), (106.0, 107.0, 100.0, 101.0)]);
+    assert_eq!(
+        find(&signal, Pattern::Engulfing).unwrap().direction,
+        Direction::Bearish
+    );
+
+    // 实体没有包住
+    let signal = detect(&[(105.0, 106.0, 101.0, 102.0), (103.0, 108.0, 100.0, 104.0)]);
+    assert!(find(&signal, Pattern::Engulfing).is_none());
+}
+
+#[test]
+fn test_pattern2() {
+    // 下跌之后的锤子线
+    let signal = detect(&[
+        (110.0, 111.0, 107.0, 108.0),
+        (108.0, 109.0, 104.0, 105.0),
+        (105.0, 106.0, 101.0, 102.0),
+        (100.0, 101.1, 92.0, 101.0),
+    ]);
+    let v = find(&signal, Pattern::Hammer).unwrap();
+    assert_eq!(v.direction, Direction::Bullish);
+    assert!((v.strength - 8.0 / 9.1).abs() < 1e-9);
+    assert!(find(&signal, Pattern::ShootingStar).is_none());
+
+    // 上涨之后的射击之星
+    let signal = detect(&[
+        (100.0, 103.0, 99.0, 102.0),
+        (102.0, 105.0, 101.0, 104.0),
+        (104.0, 107.0, 103.0, 106.0),
+        (107.0, 115.0, 105.9, 106.0),
+    ]);
+    assert_eq!(
+        find(&signal, Pattern::ShootingStar).unwrap().direction,
+        Direction::Bearish
+    );
+    assert!(find(&signal, Pattern::Hammer).is_none());
+
+    // 十字星
+    let signal = detect(&[(100.0, 105.0, 95.0, 100.0)]);
+    let v = find(&signal, Pattern::Doji).unwrap();
+    assert_eq!(v.direction, Direction::Neutral);
+    assert_eq!(v.strength, 1.0);
+}
+
+#[test]
+fn test_pattern3() {
+    // 启明星
+    let signal = detect(&[
+        (110.0, 111.0, 99.0, 100.0),
+        (99.0, 100.0, 97.0, 98.5),
+        (99.0, 108.0, 98.0, 107.5),
+    ]);
+    let v = find(&signal, Pattern::MorningStar).unwrap();
+    assert_eq!(v.direction, Direction::Bullish);
+    assert!((v.strength - 0.5).abs() < 1e-9);
+
+    // 黄昏星
+    let signal = detect(&[
+        (100.0, 111.0, 99.0, 110.0),
+        (111.0, 113.0, 110.0, 111.5),
+        (111.0, 112.0, 101.0, 102.0),
+    ]);
+    assert_eq!(
+        find(&signal, Pattern::EveningStar).unwrap().direction,
+        Direction::Bearish
+    );
+
+    // 红三兵
+    let signal = detect(&[
+        (100.0, 104.5, 99.5, 104.0),
+        (103.0, 108.5, 102.5, 108.0),
+        (107.0, 112.5, 106.5, 112.0),
+    ]);
+    assert_eq!(
+        find(&signal, Pattern::ThreeWhiteSoldiers)
+            .unwrap()
+            .direction,
+        Direction::Bullish
+    );
+
+    // 三只乌鸦
+    let signal = detect(&[
+        (112.0, 112.5, 107.5, 108.0),
+        (109.0, 109.5, 103.5, 104.0),
+        (105.0, 105.5, 99.5, 100.0),
+    ]);
+    assert_eq!(
+        find(&signal, Pattern::ThreeBlackCrows).unwrap().direction,
+        Direction::Bearish
+    );
+
+    // 内包线
+    let signal = detect(&[(100.0, 110.0, 90.0, 105.0), (102.0, 105.0, 95.0, 103.0)]);
+    let v = find(&signal, Pattern::InsideBar).unwrap();
+    assert_eq!(v.direction, Direction::Neutral);
+    assert!((v.strength - 0.5).abs() < 1e-9);
+
+    // 数据不足
+    assert!(detect(&[]).is_empty());
+}
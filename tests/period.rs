@@ -0,0 +1,121 @@
+use auto_trading::*;
+
+/// 2023-01-29 00:00:00 UTC，星期日。
+const START: u64 = 1674950400000;
+
+const DAY: u64 = 86_400_000;
+
+/// 每天一根 k 线，收盘价每天加 1，新的数据在前面。
+fn k(count: usize) -> Vec<K> {
+    (0..count)
+        .map(|i| K {
+            time: START + i as u64 * DAY,
+            open: 100.0 + i as f64,
+            high: 100.0 + i as f64,
+            low: 100.0 + i as f64,
+            close: 100.0 + i as f64,
+        })
+        .rev()
+        .collect()
+}
+
+/// 第一天以 100 开多 1 个，第六天以 105 平仓。
+fn history() -> Vec<Position> {
+    let text = format!(
+        r#"{{"product":"BTC-USDT-SWAP","lever":1,"side":"BuyLong","open_price":100.0,"quantity":1.0,"margin":100.0,"liquidation_price":0.0,"close_price":105.0,"profit":5.0,"profit_ratio":0.05,"fee":0.0,"open_time":{0},"close_time":{1},"log":[
+            {{"side":"BuyLong","price":100.0,"quantity":1.0,"margin":100.0,"fee":0.0,"profit":0.0,"profit_ratio":0.0,"time":{0},"maker":false}},
+            {{"side":"BuySell","price":105.0,"quantity":1.0,"margin":100.0,"fee":0.0,"profit":5.0,"profit_ratio":0.05,"time":{1},"maker":false}}
+        ]}}"#,
+        START,
+        START + 5 * DAY
+    );
+
+    vec![serde_json::from_str(&text).unwrap()]
+}
+
+#[test]
+fn test_period1() {
+    let data = k(35);
+    let history = history();
+
+    let month = period_returns(&data, &history, 1000.0, Period::Month, 0);
+    assert_eq!(
+        month.iter().map(|v| v.label.as_str()).collect::<Vec<_>>(),
+        ["2023-01", "2023-02", "2023-03"]
+    );
+    assert_eq!(month[0].number, 1);
+    assert_eq!(month[0].start_time, START);
+    assert_eq!(month[0].end_time, START + 2 * DAY);
+    assert_eq!(month[0].end_equity, 1002.0);
+    assert_eq!(month[0].profit, 2.0);
+    assert_eq!(month[0].realized, 0.0);
+    assert_eq!(month[1].start_equity, 1002.0);
+    assert_eq!(month[1].profit, 3.0);
+    assert_eq!(month[1].profit_ratio, 3.0 / 1002.0);
+    assert_eq!(month[1].realized, 5.0);
+    assert_eq!(month[2].profit, 0.0);
+
+    // 2023-01-29 是 2023 年第 4 周的星期日
+    let week = period_returns(&data, &history, 1000.0, Period::Week, 0);
+    assert_eq!(week[0].label, "2023-W04");
+    assert_eq!(week[0].end_time, START);
+    assert_eq!(week[1].label, "2023-W05");
+    assert_eq!(week[1].profit, 5.0);
+
+    let day = period_returns(&data, &history, 1000.0, Period::Day, 0);
+    assert_eq!(day.len(), 35);
+    assert_eq!(day[0].label, "2023-01-29");
+    assert_eq!(day[0].number, 29);
+
+    let year = period_returns(&data, &history, 1000.0, Period::Year, 0);
+    assert_eq!(year.len(), 1);
+    assert_eq!(year[0].label, "2023");
+    assert_eq!(year[0].profit, 5.0);
+
+    // 西一区，2 月 1 日 0 点属于 1 月
+    let month = period_returns(&data, &history, 1000.0, Period::Month, -60);
+    assert_eq!(month[0].end_equity, 1003.0);
+
+    assert!(period_returns([], &history, 1000.0, Period::Day, 0).is_empty());
+}
+
+#[test]
+fn test_period2() {
+    let data = k(35);
+    let history = history();
+    let breakdown = Breakdown::new(&data, &history, 1000.0, 0);
+    assert_eq!(breakdown.get(Period::Month), breakdown.month);
+
+    let stats = breakdown.stats(Period::Month);
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.profitable, 2);
+    assert_eq!(stats.profitable_ratio, 2.0 / 3.0);
+    assert_eq!(stats.best.unwrap().label, "2023-02");
+    assert_eq!(stats.worst.unwrap().label, "2023-03");
+
+    let stats = breakdown.stats(Period::Day);
+    assert_eq!(stats.profitable, 5);
+    assert_eq!(PeriodStats::new([]), PeriodStats::default());
+
+    assert_eq!(breakdown.monthly.len(), 1);
+    let row = &breakdown.monthly[0];
+    assert_eq!(row.year, 2023);
+    assert_eq!(row.month[0], Some(0.002));
+    assert_eq!(row.month[1], Some(3.0 / 1002.0));
+    assert_eq!(row.month[2], Some(0.0));
+    assert_eq!(row.month[3], None);
+    assert!((row.total - 0.005).abs() < 1e-12);
+
+    let html = Report::new(&data, &history)
+        .initial_margin(1000.0)
+        .timezone(0)
+        .to_html();
+    assert!(html.contains(r#""monthly":[{"year":2023,"month":[0.002,"#));
+    assert!(html.contains(r#"["Month",{"count":3,"profitable":2,"#));
+
+    // 默认使用 UTC，和运行的机器的时区无关
+    let default = Report::new(&data, &history)
+        .initial_margin(1000.0)
+        .to_html();
+    assert_eq!(default, html);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+#[test]
+fn test_report1() {
+    let equity = [90.0, 120.0, 100.0, 110.0];
+    let result = drawdown(equity);
+    assert_eq!(result[0], 90.0 / 120.0 - 1.0);
+    assert_eq!(result[1], 0.0);
+    assert_eq!(result[2], 100.0 / 110.0 - 1.0);
+    assert_eq!(result[3], 0.0);
+    assert!(drawdown([]).is_empty());
+    assert!(equity_curve([], [], 1000.0).is_empty());
+
+    let html = Report::new(&[], &[]).title("</title>").to_html();
+    assert!(html.contains("&lt;/title&gt;"));
+    assert!(!html.contains("{klinecharts}"));
+
+    // 替换进去的内容不会再被替换
+    let html = Report::new(&[], &[])
+        .title("{report} {klinecharts}")
+        .overlay("{klinecharts}", [])
+        .to_html();
+    assert!(html.contains("<title>{report} {klinecharts}</title>"));
+    assert!(html.contains(r#"{"name":"{klinecharts}","value":[]}"#));
+}
+
+#[tokio::test]
+async fn test_report2() {
+    let close = [
+        100.0, 100.0, 103.0, 106.0, 100.0, 97.0, 94.0, 100.0, 98.0, 80.0,
+    ];
+    let data = k(&close);
+
+    let (history, event) = backtest(
+        &close,
+        config().quantity(Unit::Quantity(1.0)),
+        |cx: &mut Context| match cx.close.len() {
+            2 | 5 => {
+                cx.order_condition(
+                    Side::BuyLong,
+                    0.0,
+                    Unit::Ignore,
+                    Unit::Ignore,
+                    Unit::Proportion(0.05),
+                    Unit::Proportion(0.05),
+                    Unit::Ignore,
+                    Unit::Ignore,
+                )
+                .unwrap();
+            }
+            8 => {
+                cx.order(Side::BuyLong, 0.0).unwrap();
+            }
+            _ => {}
+        },
+    )
+    .await;
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].close_price, 105.0);
+    assert_eq!(history[1].close_price, 95.0);
+
+    let report = Report::new(&data, &history)
+        .event(&event)
+        .initial_margin(1000.0)
+        .title("report")
+        .overlay("sma", sma_series(&data))
+        .pane("rsi", "rsi", vec![50.0; close.len()])
+        .pane("rsi", "signal", vec![30.0]);
+
+    let summary = report.summary();
+    let profit = history.iter().map(|v| v.profit - v.fee).sum::<f64>();
+    assert_eq!(summary.trade, 3);
+    assert_eq!(summary.win, 1);
+    assert_eq!(summary.loss, 2);
+    assert!((summary.profit - profit).abs() < 1e-9);
+    assert!((summary.final_equity - 1000.0 - profit).abs() < 1e-9);
+    assert!(summary.max_drawdown > 0.0);
+
+    let equity = equity_curve(&data, &history, 1000.0);
+    assert_eq!(equity.len(), close.len());
+    assert_eq!(equity[close.len() - 1], 1000.0);
+    assert!((equity[0] - summary.final_equity).abs() < 1e-9);
+
+    // 持有第一个仓位时的未实现盈亏
+    assert!((equity[close.len() - 3] - (1000.0 - history[0].fee + 3.0)).abs() < 1e-6);
+
+    let html = report.to_html();
+    assert!(html.contains(r#""exit":"StopProfit""#));
+    assert!(html.contains(r#""exit":"StopLoss""#));
+    assert!(html.contains(r#""exit":"Liquidation""#));
+    assert!(html.contains(
+        r#"{"name":"signal","value":[null,null,null,null,null,null,null,null,null,30.0]}"#
+    ));
+    assert!(!html.contains("{report}"));
+
+    // 没有事件时无法区分止盈止损
+    let html = Report::new(&data, &history).to_html();
+    assert!(!html.contains(r#""exit":"StopProfit""#));
+}
+
+/// 新的数据在前面。
+fn sma_series(k: &[K]) -> Vec<f64> {
+    let close = k.iter().map(|v| v.close).collect::<Vec<_>>();
+    (0..close.len())
+        .map(|i| sma(Source::new(&close[i..]), 3))
+        .collect()
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+#[test]
+fn test_rule1() {
+    assert!(Rule::parse("cci(close, 20) <= -350").is_ok());
+    assert!(Rule::parse("(close[1] + high) / 2 > ema(close, 3) && !(low < 1)").is_ok());
+    assert!(Rule::parse("crossover(close, sma(close, 3)) || abs(close - open) >= 2").is_ok());
+    assert!(Rule::parse("foo(close, 20) > 1").is_err());
+    assert!(Rule::parse("cci(close) > 1").is_err());
+    assert!(Rule::parse("cci(1, 20) > 1").is_err());
+    assert!(Rule::parse("cci(close, 2.5) > 1").is_err());
+    assert!(Rule::parse("close[").is_err());
+    assert!(Rule::parse("close >").is_err());
+    assert!(Rule::parse("close > 1 )").is_err());
+    assert!(Rule::parse("close # 1").is_err());
+    assert!(Rule::parse("crossover(close[100000000000000000000], 1)").is_err());
+    assert!(Rule::parse("sma(close, 100000000000000000000) > 1").is_err());
+    assert!(Rule::parse("close[4294967295] > 1").is_ok());
+}
+
+#[tokio::test]
+async fn test_rule2() {
+    let close = [100.0, 102.0, 101.0, 105.0, 103.0, 104.0, 99.0, 98.0, 100.0];
+    let rule = [
+        Rule::parse("cci(close, 3)").unwrap(),
+        Rule::parse("-(close[1] + high) / 2 + ema(close, 3) * 2 % 7").unwrap(),
+        Rule::parse("crossover(close, sma(close, 3))").unwrap(),
+        Rule::parse("close > close[1] && !(low < 90) || high == 0").unwrap(),
+        Rule::parse("dif(close, 2, 3, 2) - macd(close, 2, 3, 2) + atr(2)").unwrap(),
+        Rule::parse("crossover(close[4294967295], 1)").unwrap(),
+    ];
+    let mut count = 0;
+
+    backtest(
+        &close,
+        Config::new().initial_margin(1000.0),
+        |cx: &mut Context| {
+            let expected = [
+                cci(cx.close, 3),
+                -(cx.close[1] + cx.high[0]) / 2.0 + ema(cx.close, 3) * 2.0 % 7.0,
+                if crossover_map(cx.close, 0.0, |v| v[0] - sma(v, 3)) {
+                    1.0
+                } else {
+                    0.0
+                },
+                if cx.close > cx.close[1] { 1.0 } else { 0.0 },
+                macd(cx.close, 2, 3, 2).0 - macd(cx.close, 2, 3, 2).2
+                    + atr(cx.high, cx.low, cx.close, 2),
+                0.0,
+            ];
+
+            for (rule, expected) in rule.iter().zip(expected) {
+                let value = rule.eval(cx);
+                assert!(
+                    (value.is_nan() && expected.is_nan()) || (value - expected).abs() < 1e-9,
+                    "{}: {} != {}",
+                    rule.text(),
+                    value,
+                    expected,
+                );
+            }
+
+            count += 1;
+        },
+    )
+    .await;
+    assert_eq!(count, close.len());
+}
+
+#[tokio::test]
+async fn test_rule3() {
+    let config = RuleConfig::from_toml(
+        r#"
+        product = "BTC-USDT-SWAP"
+        level = "Minute1"
+        entry_long = "close < close[1] && close[1] < close[2]"
+        exit_long = "close >= 102"
+        quantity = { Quantity = 1.0 }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.level, Level::Minute1);
+    assert_eq!(config.k_level, None);
+    assert_eq!(config.entry_short, None);
+    assert_eq!(config.stop_loss, Unit::Ignore);
+    assert_eq!(
+        RuleConfig::from_json(serde_json::to_string(&config).unwrap()).unwrap(),
+        config
+    );
+
+    let backtester = Backtester::new(
+        exchange(&[100.0, 99.0, 98.0, 101.0, 102.0, 103.0, 104.0]),
+        Config::new().initial_margin(1000.0),
+    );
+    let (position, _) = backtester.start_rule(&config, 0).await.unwrap();
+    assert_eq!(position.len(), 1);
+    assert_eq!(position[0].side, Side::BuyLong);
+    assert_eq!(position[0].open_price, 98.0);
+    assert_eq!(position[0].close_price, 102.0);
+
+    // 规则错误
+    let mut config = config;
+    config.exit_long = Some("close >=".to_string());
+    assert!(backtester.start_rule(&config, 0).await.is_err());
+    assert!(RuleConfig::from_toml("product = 1").is_err());
+}
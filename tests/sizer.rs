@@ -0,0 +1,159 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+
+#[test]
+fn test_round_quantity() {
+    assert_eq!(round_quantity(0.35, 100.0, 0.1, 0.0), 0.30000000000000004);
+    assert_eq!(round_quantity(0.3, 100.0, 0.1, 0.0), 0.30000000000000004);
+    assert_eq!(round_quantity(0.09, 100.0, 0.1, 0.0), 0.0);
+    assert_eq!(round_quantity(0.5, 100.0, 0.1, 100.0), 0.0);
+    assert_eq!(round_quantity(1.26, 100.0, 0.0, 100.0), 1.26);
+    assert_eq!(round_quantity(f64::NAN, 100.0, 0.1, 0.0), 0.0);
+    assert_eq!(round_quantity(-1.0, 100.0, 0.1, 0.0), 0.0);
+}
+
+#[test]
+fn test_kelly() {
+    let kelly = Kelly::new(0.6, 2.0, 0.5);
+    assert!((kelly.ratio() - 0.5 * (0.6 - 0.4 / 2.0)).abs() < 1e-12);
+    assert_eq!(Kelly::new(0.3, 1.0, 1.0).ratio(), 0.0);
+
+    let position = |profit: f64| {
+        Position {
+        profit,
+        ..serde_json::from_str::<Position>(
+            r#"{"product":"BTC-USDT-SWAP","lever":1,"side":"BuyLong","open_price":1.0,"quantity":1.0,"margin":1.0,"liquidation_price":0.0,"close_price":1.0,"profit":0.0,"profit_ratio":0.0,"fee":0.0,"open_time":0,"close_time":0,"log":[]}"#,
+        )
+        .unwrap()
+    }
+    };
+    let kelly = Kelly::from_history(
+        &[
+            position(30.0),
+            position(-10.0),
+            position(10.0),
+            position(-20.0),
+        ],
+        1.0,
+    )
+    .unwrap();
+    assert_eq!(kelly.win_rate, 0.5);
+    assert_eq!(kelly.payoff, 20.0 / 15.0);
+
+    assert!(Kelly::from_history(&[], 1.0).is_err());
+
+    // 没有亏损的仓位时按照胜率开仓
+    let kelly = Kelly::from_history(&[position(30.0), position(0.0)], 0.5).unwrap();
+    assert_eq!(kelly.payoff, f64::INFINITY);
+    assert_eq!(kelly.ratio(), 0.25);
+
+    // 没有盈利的仓位时不开仓
+    let kelly = Kelly::from_history(&[position(-10.0)], 0.5).unwrap();
+    assert_eq!(kelly.payoff, 0.0);
+    assert_eq!(kelly.ratio(), 0.0);
+}
+
+#[tokio::test]
+async fn test_sizer() {
+    let mut quantity = Vec::new();
+
+    let (position, _) = backtest(
+        &[100.0, 100.0, 100.0, 100.0, 110.0, 110.0],
+        config(),
+        |cx: &mut Context| {
+            quantity.push((
+                cx.equity(),
+                cx.sizer_quantity(&FixedFractional::new(2.0), 0.0),
+                cx.sizer_quantity(&FixedRisk::new(0.01, Unit::Proportion(0.05)), 0.0),
+                cx.sizer_quantity(&FixedRisk::new(0.01, Unit::Quantity(3.0)), 200.0),
+                cx.sizer_quantity(&VolatilityTarget::new(0.01, 3, 2.0), 0.0),
+                cx.sizer_quantity(&Kelly::new(0.6, 2.0, 0.5), 0.0),
+            ));
+            match cx.close.len() {
+                4 => {
+                    cx.order_sizer(Side::BuyLong, 0.0, &FixedFractional::new(2.0))
+                        .unwrap();
+                }
+                6 => {
+                    assert!(cx
+                        .order_sizer(Side::BuyLong, 0.0, &FixedFractional::new(0.0))
+                        .is_err());
+                }
+                _ => {}
+            }
+        },
+    )
+    .await;
+
+    // 数据不足时无法计算平均真实波幅
+    assert_eq!(quantity[0], (1000.0, 20.0, 2.0, 3.33, 0.0, 2.0));
+    assert_eq!(quantity[2].4, 2.5);
+    // 开仓之后权益包含未实现盈亏
+    assert_eq!(quantity[5].0, 1200.0);
+    assert_eq!(quantity[5].1, 21.81);
+    assert_eq!(quantity[5].3, 4.0);
+    assert!(position.is_empty());
+}
+
+#[tokio::test]
+async fn test_sizer_condition() {
+    let (position, _) = backtest(
+        &[100.0, 100.0, 100.0, 95.0],
+        config(),
+        |cx: &mut Context| {
+            if cx.close.len() == 1 {
+                // 止损距离和计算开仓数量使用的止损距离一致
+                let sizer = FixedRisk::new(0.01, Unit::Quantity(3.0));
+                let stop = sizer.stop_loss_condition(cx, Side::BuyLong, 0.0);
+                assert_eq!(stop, Unit::Quantity(97.0));
+                cx.order_sizer_condition(
+                    Side::BuyLong,
+                    0.0,
+                    &sizer,
+                    Unit::Ignore,
+                    stop,
+                    Unit::Ignore,
+                    Unit::Ignore,
+                )
+                .unwrap();
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(position.len(), 1);
+    assert_eq!(position[0].quantity, 3.33);
+    assert_eq!(position[0].close_price, 97.0);
+}
+
+#[tokio::test]
+async fn test_sizer_resting() {
+    let mut id = 0;
+    let mut quantity = Vec::new();
+
+    let (position, _) = backtest(
+        &[100.0, 100.0, 100.0, 100.0],
+        config().quantity(Unit::Quantity(10.0)).open_fee(0.001),
+        |cx: &mut Context| {
+            match cx.close.len() {
+                1 => id = cx.order(Side::BuyLong, 50.0).unwrap(),
+                3 => assert!(cx.cancel(id)),
+                _ => {}
+            }
+            quantity.push((
+                cx.equity(),
+                cx.sizer_quantity(&FixedFractional::new(1.0), 0.0),
+            ));
+        },
+    )
+    .await;
+
+    // 挂单占用的保证金和手续费不能再用来开仓
+    assert_eq!(quantity[0], (949.5, 9.49));
+    assert_eq!(quantity[1], (949.5, 9.49));
+    // 撤单之后退还
+    assert_eq!(quantity[2], (1000.0, 10.0));
+    assert!(position.is_empty());
+}
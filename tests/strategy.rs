@@ -0,0 +1,227 @@
+mod common;
+
+use auto_trading::*;
+use common::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    call: Arc<Mutex<Vec<String>>>,
+}
+
+impl Strategy for Recorder {
+    fn on_start(&mut self, cx: &mut Context) {
+        self.call
+            .lock()
+            .unwrap()
+            .push(format!("start {}", cx.close.len()));
+    }
+
+    fn on_bar(&mut self, cx: &mut Context) {
+        self.call
+            .lock()
+            .unwrap()
+            .push(format!("bar {}", cx.close[0]));
+
+        if cx.time == 60_000 {
+            cx.order_profit_loss(Side::BuyLong, 0.0, Unit::Quantity(105.0), Unit::Ignore)
+                .unwrap();
+        }
+    }
+
+    fn on_order_update(&mut self, cx: &mut Context, event: &Event) {
+        match event {
+            Event::Accept { id, .. } => self.call.lock().unwrap().push(format!("accept {}", id)),
+            Event::Fill { id, record, .. } => self
+                .call
+                .lock()
+                .unwrap()
+                .push(format!("fill {} {}", id, record.price)),
+            Event::Reject { .. } => self.call.lock().unwrap().push("reject".to_string()),
+            _ => {}
+        }
+
+        // 在回调中委托，产生的事件继续分发
+        if let Event::Fill { record, .. } = event {
+            if record.side == Side::BuySell {
+                assert!(cx.order(Side::BuySell, 0.0).is_err());
+            }
+        }
+    }
+
+    fn on_position_closed(&mut self, _: &mut Context, position: &Position) {
+        self.call.lock().unwrap().push(format!(
+            "closed {}",
+            position.close_price - position.open_price
+        ));
+    }
+
+    fn on_finish(&mut self, cx: &mut Context) {
+        self.call
+            .lock()
+            .unwrap()
+            .push(format!("finish {}", cx.close.len()));
+    }
+}
+
+#[tokio::test]
+async fn test_strategy1() {
+    let recorder = Recorder::default();
+    let call = recorder.call.clone();
+    let (position, event) = backtest(
+        &[100.0, 100.0, 110.0],
+        config().quantity(Unit::Quantity(1.0)),
+        recorder,
+    )
+    .await;
+    assert_eq!(position.len(), 1);
+    assert_eq!(
+        *call.lock().unwrap(),
+        vec![
+            "start 0",
+            "bar 100",
+            "accept 1",
+            "fill 1 100",
+            "bar 100",
+            "bar 110",
+            "fill 1 105",
+            "closed 5",
+            "reject",
+            "finish 3",
+        ],
+    );
+    assert!(matches!(event.last(), Some(Event::Reject { .. })));
+}
+
+#[tokio::test]
+async fn test_strategy2() {
+    // 闭包作为策略
+    let mut time = Vec::new();
+    backtest(
+        &[100.0, 101.0, 102.0],
+        Config::new().initial_margin(1000.0),
+        |cx: &mut Context| time.push(cx.time),
+    )
+    .await;
+    assert_eq!(time, vec![60_000, 120_000, 180_000]);
+}
+
+struct Multi {
+    call: Arc<Mutex<Vec<(u64, usize, f64)>>>,
+}
+
+impl Strategy for Multi {
+    fn levels(&self) -> Vec<Level> {
+        vec![Level::Minute5]
+    }
+
+    fn on_bar(&mut self, cx: &mut Context) {
+        assert_eq!(cx.series(Level::Minute1).unwrap().close[0], cx.close[0]);
+
+        // 没有声明的时间级别
+        assert!(cx.series(Level::Hour1).is_err());
+
+        // 只能看到已经结束的 k 线
+        let series = cx.series(Level::Minute5).unwrap();
+        assert!(
+            series.close.is_empty()
+                || k_time_convert(series.time, Level::Minute5).1 <= cx.time + 60_000
+        );
+
+        self.call.lock().unwrap().push((
+            cx.time / 60_000,
+            series.close.len(),
+            series.close.first().copied().unwrap_or(0.0),
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_strategy3() {
+    let call = Arc::new(Mutex::new(Vec::new()));
+    backtest(
+        &(1..=12).map(|v| v as f64).collect::<Vec<_>>(),
+        Config::new().initial_margin(1000.0),
+        Multi { call: call.clone() },
+    )
+    .await;
+    assert_eq!(
+        *call.lock().unwrap(),
+        vec![
+            (1, 0, 0.0),
+            (2, 0, 0.0),
+            (3, 0, 0.0),
+            (4, 1, 4.0),
+            (5, 1, 4.0),
+            (6, 1, 4.0),
+            (7, 1, 4.0),
+            (8, 1, 4.0),
+            (9, 2, 9.0),
+            (10, 2, 9.0),
+            (11, 2, 9.0),
+            (12, 2, 9.0),
+        ],
+    );
+}
+
+#[derive(Default)]
+struct Register {
+    ema: Option<IndicatorId<EMA>>,
+    rsi: Option<IndicatorId<RSI>>,
+    count: Arc<Mutex<usize>>,
+}
+
+impl Strategy for Register {
+    fn on_start(&mut self, cx: &mut Context) {
+        self.ema = Some(cx.register("ema", EMA::new(3), |k| k.close).unwrap());
+    }
+
+    fn on_bar(&mut self, cx: &mut Context) {
+        // 中途注册的指标使用已经结束的 k 线初始化
+        if cx.close.len() == 5 {
+            self.rsi = Some(cx.register("rsi", RSI::new(3), |k| k.close).unwrap());
+        }
+
+        let value = cx.indicator(self.ema.unwrap()).value();
+        let expected = ema(cx.close, 3);
+        assert!((value.is_nan() && expected.is_nan()) || (value - expected).abs() < 1e-9);
+
+        if let Some(id) = self.rsi {
+            assert!((cx.indicator(id).value() - rsi(cx.close, 3)).abs() < 1e-9);
+            *self.count.lock().unwrap() += 1;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_strategy4() {
+    let strategy = Register::default();
+    let count = strategy.count.clone();
+    backtest(
+        &[100.0, 102.0, 101.0, 105.0, 103.0, 104.0, 99.0, 98.0, 100.0],
+        Config::new().initial_margin(1000.0),
+        strategy,
+    )
+    .await;
+    assert_eq!(*count.lock().unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_strategy5() {
+    let mut count = 0;
+    backtest(
+        &[100.0, 102.0, 101.0, 105.0, 103.0, 104.0, 99.0, 98.0, 100.0],
+        Config::new().initial_margin(1000.0),
+        |cx: &mut Context| {
+            // 闭包策略每根 k 线都会注册，相同的名称返回已经注册的指标
+            let id = cx.register("ema", EMA::new(3), |k| k.close).unwrap();
+            let value = cx.indicator(id).value();
+            let expected = ema(cx.close, 3);
+            assert!((value.is_nan() && expected.is_nan()) || (value - expected).abs() < 1e-9);
+            assert!(cx.register("ema", RSI::new(3), |k| k.close).is_err());
+            count += 1;
+        },
+    )
+    .await;
+    assert_eq!(count, 9);
+}
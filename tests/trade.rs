@@ -0,0 +1,181 @@
+use auto_trading::*;
+
+#[test]
+fn test_parse_trade_csv1() {
+    // 币安 trades，没有表头，微秒时间戳
+    let text = "\
+1,20000.0,0.1,2000.0,1700000000000000,true,true
+2,20010.0,0.2,4002.0,1700000001000000,false,true
+";
+    let trade = parse_trade_csv(text, TradeFormat::Binance).unwrap();
+    assert!(
+        trade
+            == vec![
+                Trade {
+                    time: 1700000001000,
+                    price: 20010.0,
+                    size: 0.2,
+                    side: TradeSide::Buy,
+                },
+                Trade {
+                    time: 1700000000000,
+                    price: 20000.0,
+                    size: 0.1,
+                    side: TradeSide::Sell,
+                },
+            ],
+        "{:#?}",
+        trade
+    );
+}
+
+#[test]
+fn test_parse_trade_csv2() {
+    // 币安 aggTrades，有表头
+    let text = "\
+agg_trade_id,price,quantity,first_trade_id,last_trade_id,transact_time,is_buyer_maker
+1,20000.0,0.1,1,2,1700000000000,false
+2,19990.0,0.3,3,3,1700000000500,true
+";
+    let trade = parse_trade_csv(text, TradeFormat::BinanceAgg).unwrap();
+    assert!(trade.len() == 2, "{:#?}", trade);
+    assert!(trade[0].time == 1700000000500, "{:#?}", trade);
+    assert!(trade[0].price == 19990.0, "{:#?}", trade);
+    assert!(trade[0].size == 0.3, "{:#?}", trade);
+    assert!(trade[0].side == TradeSide::Sell, "{:#?}", trade);
+    assert!(trade[1].side == TradeSide::Buy, "{:#?}", trade);
+}
+
+#[test]
+fn test_parse_trade_csv3() {
+    // 欧易，根据表头读取
+    let text = "\
+instrument_name,trade_id,side,price,size,created_time
+BTC-USDT-SWAP,1,buy,20000.0,1,1700000000000
+BTC-USDT-SWAP,2,sell,19990.0,2,1700000000100
+";
+    let trade = parse_trade_csv(text, TradeFormat::Okx).unwrap();
+    assert!(trade.len() == 2, "{:#?}", trade);
+    assert!(trade[0].time == 1700000000100, "{:#?}", trade);
+    assert!(trade[0].side == TradeSide::Sell, "{:#?}", trade);
+    assert!(trade[1].price == 20000.0, "{:#?}", trade);
+    assert!(trade[1].size == 1.0, "{:#?}", trade);
+    // 没有表头
+    assert!(parse_trade_csv("1,buy,20000.0,1,1700000000000", TradeFormat::Okx).is_err());
+}
+
+#[test]
+fn test_parse_trade_csv4() {
+    // 错误信息中的行号为文件中的行号，包含表头和空行
+    let text = "\
+agg_trade_id,price,quantity,first_trade_id,last_trade_id,transact_time,is_buyer_maker
+
+1,20000.0,0.1,1,2,1700000000000,false
+2,abc,0.3,3,3,1700000000500,true
+";
+    let error = parse_trade_csv(text, TradeFormat::BinanceAgg).unwrap_err();
+    assert!(error.to_string().starts_with("line 4:"), "{}", error);
+    // 1 分钟级别的时间戳保持不变
+    assert!(k_time_convert(30_000, Level::Minute1) == (30_000, 90_000));
+}
+
+#[test]
+fn test_trade_to_k() {
+    let trade = [
+        (120_000, 103.0),
+        (61_000, 99.0),
+        (60_000, 101.0),
+        (30_000, 104.0),
+        (10_000, 98.0),
+        (0, 100.0),
+    ]
+    .map(|(time, price)| Trade {
+        time,
+        price,
+        size: 1.0,
+        side: TradeSide::Buy,
+    });
+    let k = trade_to_k(trade, Level::Minute1);
+    assert!(
+        k == vec![
+            K {
+                time: 120_000,
+                open: 103.0,
+                high: 103.0,
+                low: 103.0,
+                close: 103.0,
+            },
+            K {
+                time: 60_000,
+                open: 101.0,
+                high: 101.0,
+                low: 99.0,
+                close: 99.0,
+            },
+            K {
+                time: 0,
+                open: 100.0,
+                high: 104.0,
+                low: 98.0,
+                close: 104.0,
+            },
+        ],
+        "{:#?}",
+        k
+    );
+}
+
+#[tokio::test]
+async fn test_start_trade() {
+    // 第一根 k 线结束后以收盘价开多，止盈委托在之后的逐笔成交中触发
+    let trade = [
+        (125_000, 20300.0),
+        (120_000, 20250.0),
+        (90_000, 20150.0),
+        (61_000, 20050.0),
+        (60_000, 20000.0),
+        (0, 19900.0),
+    ]
+    .map(|(time, price)| Trade {
+        time,
+        price,
+        size: 1.0,
+        side: TradeSide::Buy,
+    });
+    let exchange =
+        LocalExchange::new().push("BTC-USDT-SWAP", Level::Minute1, Vec::new(), 0.01, 0.0);
+    let config = Config::new()
+        .initial_margin(1000.0)
+        .quantity(Unit::Quantity(0.01))
+        .lever(10);
+    let backtester = Backtester::new(exchange, config);
+    let mut call = Vec::new();
+    let result = backtester
+        .start_trade(
+            |cx| {
+                call.push((cx.time, cx.close[0]));
+                if cx.time == 0 {
+                    cx.order_profit_loss_condition(
+                        Side::BuyLong,
+                        0.0,
+                        Unit::Quantity(20100.0),
+                        Unit::Ignore,
+                        Unit::Quantity(20100.0),
+                        Unit::Ignore,
+                    )
+                    .unwrap();
+                }
+            },
+            "BTC-USDT-SWAP",
+            trade,
+            Level::Minute1,
+        )
+        .await
+        .unwrap();
+    // 最后一根 k 线没有结束，不调用策略
+    assert!(call == vec![(0, 19900.0), (60_000, 20150.0)], "{:#?}", call);
+    assert!(result.len() == 1, "{:#?}", result);
+    assert!(result[0].open_price == 19900.0, "{:#?}", result);
+    assert!(result[0].close_price == 20100.0, "{:#?}", result);
+    assert!(result[0].close_time == 90_000, "{:#?}", result);
+}